@@ -15,6 +15,7 @@ fn benchmark() {
     let primes_run = false;
     let fib_run = false;
     let huffman_run = false;
+    let huffman_decode_run = false;
     let nth_root_run = false;
     let inc_dec_run = true;
     println!("Benchmarks in non-optimised mode.");
@@ -24,6 +25,11 @@ fn benchmark() {
         huffman_bench(100);
     }
 
+    if huffman_decode_run {
+        println!("\nHuffman Decoding benchmark.");
+        huffman_decode_bench(10);
+    }
+
     if inc_dec_run {
         println!("\nIncrement and Decrement Testing.");
         inc_dec(24, 100_000_000);
@@ -195,4 +201,34 @@ fn huffman_bench(iters: u128) {
     println!("{}", d);
     println!("{} milliseconds / iteration.\n", el);
     write_to_file("./src/10millionchars-lorem-ipsum".to_owned(), &e.0, &e.1);
+}
+
+fn huffman_decode_bench(iters: u128) {
+    use crate::compsci::compression::huffman::{Encode, huffman_decode, table::DecodeTable};
+
+    let words = ["Lorem", "ipsum", "dolor", "sit", "amet,", "consectetur", "adipiscing", "elit.", "Sed", "do"];
+    let mut text = String::with_capacity(10_000_000);
+    let mut seed: u64 = 42;
+    while text.len() < 10_000_000 {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        text.push_str(words[(seed >> 60) as usize % words.len()]);
+        text.push(' ');
+    }
+    let (enc, root) = text.full_encode();
+    let mut d = String::new();
+
+    let mut now = Instant::now();
+    for _ in 0..iters {
+        d = huffman_decode(&enc, &root);
+    }
+    let mut el = now.elapsed().as_millis() / iters;
+    println!("{} milliseconds / iteration (tree walk).", el);
+
+    now = Instant::now();
+    for _ in 0..iters {
+        d = DecodeTable::from_tree(&root).unwrap().decode_string(&enc).unwrap();
+    }
+    el = now.elapsed().as_millis() / iters;
+    println!("{} milliseconds / iteration (lookup table).", el);
+    assert_eq!(d, text);
 }
\ No newline at end of file
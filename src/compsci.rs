@@ -1,7 +1,10 @@
 //! This part of LibRapid is designed for computer science - Huffman text compression, dates and other tools related to computers and data types.
+pub mod checksum;
 pub mod compression;
+pub mod encoding;
 pub mod general;
 pub mod rapidlogging;
 pub mod unit_conversion;
+pub mod varint;
 pub mod colours;
 pub mod stringhelpers;
\ No newline at end of file
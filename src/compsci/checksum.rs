@@ -0,0 +1,18 @@
+//! Checksums and non-cryptographic hash functions for detecting corrupted data and for hash maps.
+//! Every algorithm can be fed incrementally, and all of them implement `std::hash::Hasher`.
+/// Implements CRC-32 (IEEE 802.3 and Castagnoli) and CRC-64 (XZ), both table-driven and slicing-by-8.
+/// # Efficiency
+/// Slicing-by-8 processes eight bytes per step with eight lookup tables, which is several times faster than one byte per step.
+pub mod crc;
+/// Implements Adler-32, the checksum of the zlib format.
+/// # Efficiency
+/// Faster than CRC-32, but weaker on short inputs.
+pub mod adler;
+/// Implements the 32- and 64-bit variants of the Fowler-Noll-Vo hash FNV-1a.
+/// # Efficiency
+/// Very fast on short keys, which makes it a good choice for hash maps with small keys.
+pub mod fnv;
+/// Implements xxHash64, a fast hash function with excellent distribution.
+/// # Efficiency
+/// Processes 32 bytes per step, so it shines on long inputs.
+pub mod xxhash;
\ No newline at end of file
@@ -0,0 +1,98 @@
+//! The Adler-32 checksum, which consists of two 16-bit sums modulo the largest prime below 2^16.
+use std::hash::Hasher;
+
+const MOD:  u32   = 65521;
+/// The largest number of bytes after which `b` can't have overflowed yet.
+const NMAX: usize = 5552;
+
+/// An incremental Adler-32.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::checksum::adler::Adler32;
+/// let mut adler = Adler32::new();
+/// adler.update(b"Wiki");
+/// adler.update(b"pedia");
+///
+/// assert_eq!(adler.checksum(), 0x11E6_0398);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Adler32 {
+    /// Creates a new `Adler32`.
+    /// # Returns
+    /// A new `Adler32`.
+    #[must_use]
+    pub const fn new() -> Adler32 {
+        Adler32 { a: 1, b: 0 }
+    }
+    /// Continues from a checksum calculated earlier, e.g. one read from a file.
+    /// # Arguments
+    /// * `checksum` - The Adler-32 of the data so far.
+    /// # Returns
+    /// A new `Adler32`.
+    #[must_use]
+    pub const fn from_checksum(checksum: u32) -> Adler32 {
+        Adler32 { a: checksum & 0xFFFF, b: checksum >> 16 }
+    }
+    /// Feeds data into the checksum.
+    /// # Arguments
+    /// * `data` - The data.
+    pub fn update(&mut self, data: &[u8]) {
+        for chunk in data.chunks(NMAX) {
+            for x in chunk {
+                self.a += *x as u32;
+                self.b += self.a;
+            }
+            self.a %= MOD;
+            self.b %= MOD;
+        }
+    }
+    /// Get the checksum of all data fed in so far. More data can still be fed in afterwards.
+    /// # Returns
+    /// A `u32`.
+    #[must_use]
+    pub const fn checksum(&self) -> u32 {
+        self.b << 16 | self.a
+    }
+    /// Resets the checksum to its initial state.
+    pub fn reset(&mut self) {
+        *self = Adler32::new();
+    }
+}
+
+impl Default for Adler32 {
+    fn default() -> Adler32 {
+        Adler32::new()
+    }
+}
+
+impl Hasher for Adler32 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+    fn finish(&self) -> u64 {
+        self.checksum() as u64
+    }
+}
+
+/// Calculates the Adler-32 of `data`, as used by zlib.
+/// # Arguments
+/// * `data` - The data.
+/// # Returns
+/// A `u32`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::checksum::adler::adler32;
+/// assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+/// assert_eq!(adler32(b""), 1);
+/// ```
+#[must_use]
+pub fn adler32(data: &[u8]) -> u32 {
+    let mut adler: Adler32 = Adler32::new();
+    adler.update(data);
+    adler.checksum()
+}
\ No newline at end of file
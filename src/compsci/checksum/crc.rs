@@ -0,0 +1,298 @@
+//! Cyclic redundancy checks. All variants are reflected, start with all bits set and invert the result.
+//! The lookup tables are computed at compile time.
+use std::convert::TryInto;
+use std::hash::Hasher;
+use std::slice::ChunksExact;
+
+/// The reversed polynomial of CRC-32 (IEEE 802.3), as used by zlib, gzip, PNG and Ethernet.
+pub const CRC32_IEEE_POLY: u32 = 0xEDB8_8320;
+/// The reversed polynomial of CRC-32C (Castagnoli), as used by iSCSI, ext4 and SSE 4.2.
+pub const CRC32_CASTAGNOLI_POLY: u32 = 0x82F6_3B78;
+/// The reversed polynomial of CRC-64 (ECMA-182), as used by XZ.
+pub const CRC64_XZ_POLY: u64 = 0xC96C_5795_D787_0F42;
+
+static CRC32_IEEE_TABLES:       [[u32; 256]; 8] = crc32_tables(CRC32_IEEE_POLY);
+static CRC32_CASTAGNOLI_TABLES: [[u32; 256]; 8] = crc32_tables(CRC32_CASTAGNOLI_POLY);
+static CRC64_XZ_TABLES:         [[u64; 256]; 8] = crc64_tables(CRC64_XZ_POLY);
+
+/// Table `k` advances the CRC of a byte by `k` further zero bytes, which slicing-by-8 needs.
+const fn crc32_tables(poly: u32) -> [[u32; 256]; 8] {
+    let mut tables: [[u32; 256]; 8] = [[0; 256]; 8];
+    let mut i:      usize           = 0;
+    while i < 256 {
+        let mut c: u32 = i as u32;
+        let mut k: u8  = 0;
+        while k < 8 {
+            c = if c & 1 == 1 { poly ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        tables[0][i] = c;
+        i += 1;
+    }
+
+    let mut t: usize = 1;
+    while t < 8 {
+        i = 0;
+        while i < 256 {
+            let prev: u32 = tables[t - 1][i];
+            tables[t][i] = (prev >> 8) ^ tables[0][(prev & 0xFF) as usize];
+            i += 1;
+        }
+        t += 1;
+    }
+    tables
+}
+
+/// The 64-bit counterpart to `crc32_tables`.
+const fn crc64_tables(poly: u64) -> [[u64; 256]; 8] {
+    let mut tables: [[u64; 256]; 8] = [[0; 256]; 8];
+    let mut i:      usize           = 0;
+    while i < 256 {
+        let mut c: u64 = i as u64;
+        let mut k: u8  = 0;
+        while k < 8 {
+            c = if c & 1 == 1 { poly ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        tables[0][i] = c;
+        i += 1;
+    }
+
+    let mut t: usize = 1;
+    while t < 8 {
+        i = 0;
+        while i < 256 {
+            let prev: u64 = tables[t - 1][i];
+            tables[t][i] = (prev >> 8) ^ tables[0][(prev & 0xFF) as usize];
+            i += 1;
+        }
+        t += 1;
+    }
+    tables
+}
+
+/// An incremental CRC-32 in either the IEEE or the Castagnoli variant.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::checksum::crc::Crc32;
+/// let mut crc = Crc32::new();
+/// crc.update(b"12345");
+/// crc.update(b"6789");
+///
+/// assert_eq!(crc.checksum(), 0xCBF4_3926);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Crc32 {
+    state:  u32,
+    tables: &'static [[u32; 256]; 8],
+}
+
+impl Crc32 {
+    /// Creates a new `Crc32` using the IEEE 802.3 polynomial.
+    /// # Returns
+    /// A new `Crc32`.
+    #[must_use]
+    pub fn new() -> Crc32 {
+        Crc32 { state: !0, tables: &CRC32_IEEE_TABLES }
+    }
+    /// Creates a new `Crc32` using the Castagnoli polynomial (CRC-32C).
+    /// # Returns
+    /// A new `Crc32`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::checksum::crc::Crc32;
+    /// let mut crc = Crc32::castagnoli();
+    /// crc.update(b"123456789");
+    ///
+    /// assert_eq!(crc.checksum(), 0xE306_9283);
+    /// ```
+    #[must_use]
+    pub fn castagnoli() -> Crc32 {
+        Crc32 { state: !0, tables: &CRC32_CASTAGNOLI_TABLES }
+    }
+    /// Feeds data into the CRC, eight bytes at a time (slicing-by-8).
+    /// # Arguments
+    /// * `data` - The data.
+    pub fn update(&mut self, data: &[u8]) {
+        let t:       &[[u32; 256]; 8] = self.tables;
+        let mut crc: u32              = self.state;
+        let chunks:  ChunksExact<u8>  = data.chunks_exact(8);
+        let rest:    &[u8]            = chunks.remainder();
+
+        for chunk in chunks {
+            let one: u32 = u32::from_le_bytes(chunk[0..4].try_into().unwrap()) ^ crc;
+            let two: u32 = u32::from_le_bytes(chunk[4..8].try_into().unwrap());
+            crc = t[7][(one & 0xFF) as usize]         ^ t[6][((one >> 8) & 0xFF) as usize]
+                ^ t[5][((one >> 16) & 0xFF) as usize] ^ t[4][(one >> 24) as usize]
+                ^ t[3][(two & 0xFF) as usize]         ^ t[2][((two >> 8) & 0xFF) as usize]
+                ^ t[1][((two >> 16) & 0xFF) as usize] ^ t[0][(two >> 24) as usize];
+        }
+        self.state = crc;
+        self.update_bytewise(rest);
+    }
+    /// Feeds data into the CRC one byte at a time, using a single lookup table.
+    /// Gives the same result as `update`, which is faster on all but the shortest inputs.
+    /// # Arguments
+    /// * `data` - The data.
+    pub fn update_bytewise(&mut self, data: &[u8]) {
+        let t: &[u32; 256] = &self.tables[0];
+        for b in data
+        { self.state = t[((self.state ^ *b as u32) & 0xFF) as usize] ^ (self.state >> 8); }
+    }
+    /// Get the CRC of all data fed in so far. More data can still be fed in afterwards.
+    /// # Returns
+    /// A `u32`.
+    #[must_use]
+    pub const fn checksum(&self) -> u32 {
+        !self.state
+    }
+    /// Resets the CRC to its initial state, keeping the polynomial.
+    pub fn reset(&mut self) {
+        self.state = !0;
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Crc32 {
+        Crc32::new()
+    }
+}
+
+impl Hasher for Crc32 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+    fn finish(&self) -> u64 {
+        self.checksum() as u64
+    }
+}
+
+/// An incremental CRC-64 using the ECMA-182 polynomial, as used by XZ.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::checksum::crc::Crc64;
+/// let mut crc = Crc64::new();
+/// crc.update(b"123456789");
+///
+/// assert_eq!(crc.checksum(), 0x995D_C9BB_DF19_39FA);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Crc64 {
+    state: u64,
+}
+
+impl Crc64 {
+    /// Creates a new `Crc64`.
+    /// # Returns
+    /// A new `Crc64`.
+    #[must_use]
+    pub const fn new() -> Crc64 {
+        Crc64 { state: !0 }
+    }
+    /// Feeds data into the CRC, eight bytes at a time (slicing-by-8).
+    /// # Arguments
+    /// * `data` - The data.
+    pub fn update(&mut self, data: &[u8]) {
+        let t:       &[[u64; 256]; 8] = &CRC64_XZ_TABLES;
+        let mut crc: u64              = self.state;
+        let chunks:  ChunksExact<u8>  = data.chunks_exact(8);
+        let rest:    &[u8]            = chunks.remainder();
+
+        for chunk in chunks {
+            let x: u64 = u64::from_le_bytes(chunk.try_into().unwrap()) ^ crc;
+            crc = t[7][(x & 0xFF) as usize]         ^ t[6][((x >> 8) & 0xFF) as usize]
+                ^ t[5][((x >> 16) & 0xFF) as usize] ^ t[4][((x >> 24) & 0xFF) as usize]
+                ^ t[3][((x >> 32) & 0xFF) as usize] ^ t[2][((x >> 40) & 0xFF) as usize]
+                ^ t[1][((x >> 48) & 0xFF) as usize] ^ t[0][(x >> 56) as usize];
+        }
+        self.state = crc;
+        self.update_bytewise(rest);
+    }
+    /// Feeds data into the CRC one byte at a time, using a single lookup table.
+    /// Gives the same result as `update`, which is faster on all but the shortest inputs.
+    /// # Arguments
+    /// * `data` - The data.
+    pub fn update_bytewise(&mut self, data: &[u8]) {
+        let t: &[u64; 256] = &CRC64_XZ_TABLES[0];
+        for b in data
+        { self.state = t[((self.state ^ *b as u64) & 0xFF) as usize] ^ (self.state >> 8); }
+    }
+    /// Get the CRC of all data fed in so far. More data can still be fed in afterwards.
+    /// # Returns
+    /// A `u64`.
+    #[must_use]
+    pub const fn checksum(&self) -> u64 {
+        !self.state
+    }
+    /// Resets the CRC to its initial state.
+    pub fn reset(&mut self) {
+        self.state = !0;
+    }
+}
+
+impl Default for Crc64 {
+    fn default() -> Crc64 {
+        Crc64::new()
+    }
+}
+
+impl Hasher for Crc64 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+    fn finish(&self) -> u64 {
+        self.checksum()
+    }
+}
+
+/// Calculates the CRC-32 (IEEE 802.3) of `data`.
+/// # Arguments
+/// * `data` - The data.
+/// # Returns
+/// A `u32`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::checksum::crc::crc32;
+/// assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+/// assert_eq!(crc32(b""), 0);
+/// ```
+#[must_use]
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc: Crc32 = Crc32::new();
+    crc.update(data);
+    crc.checksum()
+}
+
+/// Calculates the CRC-32C (Castagnoli) of `data`.
+/// # Arguments
+/// * `data` - The data.
+/// # Returns
+/// A `u32`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::checksum::crc::crc32c;
+/// assert_eq!(crc32c(b"123456789"), 0xE306_9283);
+/// ```
+#[must_use]
+pub fn crc32c(data: &[u8]) -> u32 {
+    let mut crc: Crc32 = Crc32::castagnoli();
+    crc.update(data);
+    crc.checksum()
+}
+
+/// Calculates the CRC-64 (XZ) of `data`.
+/// # Arguments
+/// * `data` - The data.
+/// # Returns
+/// A `u64`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::checksum::crc::crc64;
+/// assert_eq!(crc64(b"123456789"), 0x995D_C9BB_DF19_39FA);
+/// ```
+#[must_use]
+pub fn crc64(data: &[u8]) -> u64 {
+    let mut crc: Crc64 = Crc64::new();
+    crc.update(data);
+    crc.checksum()
+}
\ No newline at end of file
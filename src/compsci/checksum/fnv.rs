@@ -0,0 +1,162 @@
+//! The Fowler-Noll-Vo hash FNV-1a, which XORs in every byte and then multiplies by a prime.
+use std::hash::{Hasher, BuildHasherDefault};
+
+const FNV32_OFFSET: u32 = 0x811C_9DC5;
+const FNV32_PRIME:  u32 = 0x0100_0193;
+const FNV64_OFFSET: u64 = 0xCBF2_9CE4_8422_2325;
+const FNV64_PRIME:  u64 = 0x0000_0100_0000_01B3;
+
+/// A `BuildHasher` for using `Fnv1a64` in a `HashMap` or `HashSet`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::checksum::fnv::FnvBuildHasher;
+/// use std::collections::HashMap;
+/// let mut map: HashMap<&str, u32, FnvBuildHasher> = HashMap::default();
+/// map.insert("one", 1);
+///
+/// assert_eq!(map["one"], 1);
+/// ```
+pub type FnvBuildHasher = BuildHasherDefault<Fnv1a64>;
+
+/// An incremental 32-bit FNV-1a hash.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::checksum::fnv::Fnv1a32;
+/// let mut fnv = Fnv1a32::new();
+/// fnv.update(b"foo");
+/// fnv.update(b"bar");
+///
+/// assert_eq!(fnv.hash(), 0xBF9C_F968);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Fnv1a32 {
+    state: u32,
+}
+
+impl Fnv1a32 {
+    /// Creates a new `Fnv1a32`.
+    /// # Returns
+    /// A new `Fnv1a32`.
+    #[must_use]
+    pub const fn new() -> Fnv1a32 {
+        Fnv1a32 { state: FNV32_OFFSET }
+    }
+    /// Feeds data into the hash.
+    /// # Arguments
+    /// * `data` - The data.
+    pub fn update(&mut self, data: &[u8]) {
+        for b in data
+        { self.state = (self.state ^ *b as u32).wrapping_mul(FNV32_PRIME); }
+    }
+    /// Get the hash of all data fed in so far. More data can still be fed in afterwards.
+    /// # Returns
+    /// A `u32`.
+    #[must_use]
+    pub const fn hash(&self) -> u32 {
+        self.state
+    }
+}
+
+impl Default for Fnv1a32 {
+    fn default() -> Fnv1a32 {
+        Fnv1a32::new()
+    }
+}
+
+impl Hasher for Fnv1a32 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+    fn finish(&self) -> u64 {
+        self.state as u64
+    }
+}
+
+/// An incremental 64-bit FNV-1a hash.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::checksum::fnv::Fnv1a64;
+/// use std::hash::Hasher;
+/// let mut fnv = Fnv1a64::new();
+/// fnv.write(b"foobar");
+///
+/// assert_eq!(fnv.finish(), 0x8594_4171_F739_67E8);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Fnv1a64 {
+    state: u64,
+}
+
+impl Fnv1a64 {
+    /// Creates a new `Fnv1a64`.
+    /// # Returns
+    /// A new `Fnv1a64`.
+    #[must_use]
+    pub const fn new() -> Fnv1a64 {
+        Fnv1a64 { state: FNV64_OFFSET }
+    }
+    /// Feeds data into the hash.
+    /// # Arguments
+    /// * `data` - The data.
+    pub fn update(&mut self, data: &[u8]) {
+        for b in data
+        { self.state = (self.state ^ *b as u64).wrapping_mul(FNV64_PRIME); }
+    }
+    /// Get the hash of all data fed in so far. More data can still be fed in afterwards.
+    /// # Returns
+    /// A `u64`.
+    #[must_use]
+    pub const fn hash(&self) -> u64 {
+        self.state
+    }
+}
+
+impl Default for Fnv1a64 {
+    fn default() -> Fnv1a64 {
+        Fnv1a64::new()
+    }
+}
+
+impl Hasher for Fnv1a64 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+    fn finish(&self) -> u64 {
+        self.state
+    }
+}
+
+/// Calculates the 32-bit FNV-1a hash of `data`.
+/// # Arguments
+/// * `data` - The data.
+/// # Returns
+/// A `u32`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::checksum::fnv::fnv1a_32;
+/// assert_eq!(fnv1a_32(b""), 0x811C_9DC5);
+/// assert_eq!(fnv1a_32(b"a"), 0xE40C_292C);
+/// ```
+#[must_use]
+pub fn fnv1a_32(data: &[u8]) -> u32 {
+    let mut fnv: Fnv1a32 = Fnv1a32::new();
+    fnv.update(data);
+    fnv.hash()
+}
+
+/// Calculates the 64-bit FNV-1a hash of `data`.
+/// # Arguments
+/// * `data` - The data.
+/// # Returns
+/// A `u64`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::checksum::fnv::fnv1a_64;
+/// assert_eq!(fnv1a_64(b"a"), 0xAF63_DC4C_8601_EC8C);
+/// ```
+#[must_use]
+pub fn fnv1a_64(data: &[u8]) -> u64 {
+    let mut fnv: Fnv1a64 = Fnv1a64::new();
+    fnv.update(data);
+    fnv.hash()
+}
\ No newline at end of file
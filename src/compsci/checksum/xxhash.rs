@@ -0,0 +1,207 @@
+//! The 64-bit variant of xxHash. Data is processed in stripes of 32 bytes by four independent lanes.
+use std::convert::TryInto;
+use std::hash::{Hasher, BuildHasher};
+use std::slice::ChunksExact;
+
+const PRIME1: u64 = 0x9E37_79B1_85EB_CA87;
+const PRIME2: u64 = 0xC2B2_AE3D_27D4_EB4F;
+const PRIME3: u64 = 0x1656_67B1_9E37_79F9;
+const PRIME4: u64 = 0x85EB_CA77_C2B2_AE63;
+const PRIME5: u64 = 0x27D4_EB2F_1656_67C5;
+const STRIPE: usize = 32;
+
+/// An incremental xxHash64.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::checksum::xxhash::{XxHash64, xxhash64};
+/// let data = b"Nobody inspects the spammish repetition";
+/// let mut xx = XxHash64::new();
+/// for chunk in data.chunks(5) {
+///     xx.update(chunk);
+/// }
+///
+/// assert_eq!(xx.hash(), 0xFBCE_A83C_8A37_8BF1);
+/// assert_eq!(xx.hash(), xxhash64(data, 0));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct XxHash64 {
+    seed:     u64,
+    lanes:    [u64; 4],
+    buffer:   [u8; STRIPE],
+    buffered: usize,
+    len:      u64,
+}
+
+impl XxHash64 {
+    /// Creates a new `XxHash64` with a seed of `0`.
+    /// # Returns
+    /// A new `XxHash64`.
+    #[must_use]
+    pub const fn new() -> XxHash64 {
+        XxHash64::with_seed(0)
+    }
+    /// Creates a new `XxHash64` with a custom seed. Different seeds give unrelated hashes.
+    /// # Arguments
+    /// * `seed` - The seed.
+    /// # Returns
+    /// A new `XxHash64`.
+    #[must_use]
+    pub const fn with_seed(seed: u64) -> XxHash64 {
+        XxHash64 { seed,
+                   lanes:    [seed.wrapping_add(PRIME1).wrapping_add(PRIME2),
+                              seed.wrapping_add(PRIME2),
+                              seed,
+                              seed.wrapping_sub(PRIME1)],
+                   buffer:   [0; STRIPE],
+                   buffered: 0,
+                   len:      0 }
+    }
+    /// Feeds data into the hash.
+    /// # Arguments
+    /// * `data` - The data.
+    pub fn update(&mut self, data: &[u8]) {
+        self.len += data.len() as u64;
+        let mut data: &[u8] = data;
+
+        if self.buffered > 0 {
+            let n: usize = data.len().min(STRIPE - self.buffered);
+            self.buffer[self.buffered..self.buffered + n].copy_from_slice(&data[..n]);
+            self.buffered += n;
+            data           = &data[n..];
+            if self.buffered < STRIPE
+            { return; }
+
+            let stripe: [u8; STRIPE] = self.buffer;
+            self.consume(&stripe);
+            self.buffered = 0;
+        }
+
+        let stripes: ChunksExact<u8> = data.chunks_exact(STRIPE);
+        let rest:    &[u8]           = stripes.remainder();
+        for stripe in stripes
+        { self.consume(stripe); }
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffered = rest.len();
+    }
+    /// Get the hash of all data fed in so far. More data can still be fed in afterwards.
+    /// # Returns
+    /// A `u64`.
+    #[must_use]
+    pub fn hash(&self) -> u64 {
+        let mut h: u64 = if self.len >= STRIPE as u64 {
+            let [v1, v2, v3, v4] = self.lanes;
+            let mut h: u64 = v1.rotate_left(1).wrapping_add(v2.rotate_left(7))
+                                              .wrapping_add(v3.rotate_left(12))
+                                              .wrapping_add(v4.rotate_left(18));
+            for v in &self.lanes
+            { h = (h ^ round(0, *v)).wrapping_mul(PRIME1).wrapping_add(PRIME4); }
+            h
+        }
+        else
+        { self.seed.wrapping_add(PRIME5) };
+        h = h.wrapping_add(self.len);
+
+        let mut rest: &[u8] = &self.buffer[..self.buffered];
+        while rest.len() >= 8 {
+            h    ^= round(0, u64::from_le_bytes(rest[..8].try_into().unwrap()));
+            h     = h.rotate_left(27).wrapping_mul(PRIME1).wrapping_add(PRIME4);
+            rest  = &rest[8..];
+        }
+        if rest.len() >= 4 {
+            h    ^= (u32::from_le_bytes(rest[..4].try_into().unwrap()) as u64).wrapping_mul(PRIME1);
+            h     = h.rotate_left(23).wrapping_mul(PRIME2).wrapping_add(PRIME3);
+            rest  = &rest[4..];
+        }
+        for b in rest {
+            h ^= (*b as u64).wrapping_mul(PRIME5);
+            h  = h.rotate_left(11).wrapping_mul(PRIME1);
+        }
+
+        h ^= h >> 33;
+        h  = h.wrapping_mul(PRIME2);
+        h ^= h >> 29;
+        h  = h.wrapping_mul(PRIME3);
+        h ^ (h >> 32)
+    }
+
+    fn consume(&mut self, stripe: &[u8]) {
+        for (lane, word) in self.lanes.iter_mut().zip(stripe.chunks_exact(8))
+        { *lane = round(*lane, u64::from_le_bytes(word.try_into().unwrap())); }
+    }
+}
+
+#[inline]
+const fn round(acc: u64, input: u64) -> u64 {
+    acc.wrapping_add(input.wrapping_mul(PRIME2))
+       .rotate_left(31)
+       .wrapping_mul(PRIME1)
+}
+
+impl Default for XxHash64 {
+    fn default() -> XxHash64 {
+        XxHash64::new()
+    }
+}
+
+impl Hasher for XxHash64 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+    fn finish(&self) -> u64 {
+        self.hash()
+    }
+}
+
+/// A `BuildHasher` for using `XxHash64` with a fixed seed in a `HashMap` or `HashSet`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::checksum::xxhash::XxBuildHasher;
+/// use std::collections::HashSet;
+/// let mut set: HashSet<u64, XxBuildHasher> = HashSet::with_hasher(XxBuildHasher::new(42));
+/// set.insert(7);
+///
+/// assert!(set.contains(&7));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct XxBuildHasher {
+    seed: u64,
+}
+
+impl XxBuildHasher {
+    /// Creates a new `XxBuildHasher`.
+    /// # Arguments
+    /// * `seed` - The seed of every hasher built.
+    /// # Returns
+    /// A new `XxBuildHasher`.
+    #[must_use]
+    pub const fn new(seed: u64) -> XxBuildHasher {
+        XxBuildHasher { seed }
+    }
+}
+
+impl BuildHasher for XxBuildHasher {
+    type Hasher = XxHash64;
+
+    fn build_hasher(&self) -> XxHash64 {
+        XxHash64::with_seed(self.seed)
+    }
+}
+
+/// Calculates the xxHash64 of `data`.
+/// # Arguments
+/// * `data` - The data.
+/// * `seed` - The seed.
+/// # Returns
+/// A `u64`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::checksum::xxhash::xxhash64;
+/// assert_eq!(xxhash64(b"", 0), 0xEF46_DB37_51D8_E999);
+/// assert_eq!(xxhash64(b"abc", 0), 0x44BC_2CF5_AD77_0999);
+/// ```
+#[must_use]
+pub fn xxhash64(data: &[u8], seed: u64) -> u64 {
+    let mut xx: XxHash64 = XxHash64::with_seed(seed);
+    xx.update(data);
+    xx.hash()
+}
\ No newline at end of file
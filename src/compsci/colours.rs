@@ -1,3 +1,6 @@
 //! Modules for handling colours.
+//! Each of the 16.7 million RGB values converts from `RGBa` to `HSV` or `HSL` and back without changing.
 pub mod cmyk;
+pub mod hsl;
+pub mod hsv;
 pub mod rgba;
\ No newline at end of file
@@ -1,5 +1,5 @@
 //! In here you'll find the struct for the `CMYK` colour-definition.
-use crate::{math::general::NumTools, compsci::colours::rgba::RGBa};
+use crate::{math::general::NumTools, compsci::colours::{hsl::HSL, hsv::HSV, rgba::RGBa}};
 
 const ARG_ERR: &str = "Arguments must range from 0 to 1.";
 /// A struct for storing CMYK-Values.
@@ -49,30 +49,31 @@ impl CMYK {
     /// A new `CMYK` struct.
     /// # Examples
     /// ```
-    /// use lib_rapid::compsci::colours::{cmyk::CMYK, rgba::RGBa};
+    /// use lib_rapid::compsci::colours::{cmyk::CMYK, hsv::HSV, rgba::RGBa};
     /// 
     /// let nice_blue_rgba = RGBa::new(128, 191, 255, 255);
     /// let nice_blue_cmyk = CMYK::from_rgb_vals(128, 191, 255);
     /// 
     /// assert_eq!(CMYK::from_rgba_struct(&nice_blue_rgba),
     ///            nice_blue_cmyk);
+    /// assert_eq!((nice_blue_cmyk.yellow(), nice_blue_cmyk.black()), (0.0, 0.0)); // Blue is at its full value.
+    ///
+    /// // Going through `HSV` gives the same colour, up to `f32` rounding.
+    /// let via_hsv = CMYK::from_hsv_struct(&HSV::from_rgba_struct(&nice_blue_rgba));
+    /// for (a, b) in [(nice_blue_cmyk.cyan(),    via_hsv.cyan()),
+    ///                (nice_blue_cmyk.magenta(), via_hsv.magenta()),
+    ///                (nice_blue_cmyk.yellow(),  via_hsv.yellow()),
+    ///                (nice_blue_cmyk.black(),   via_hsv.black())].iter() {
+    ///     assert!((a - b).abs() < 1e-6);
+    /// }
+    /// assert_eq!(RGBa::from_cmyk_struct(&nice_blue_cmyk), nice_blue_rgba);
     /// ```
     #[inline]
     #[must_use]
     pub fn from_rgba_struct(rgb: &RGBa) -> CMYK {
-        let r: f32 = rgb.red as f32 / 255.0;
-        let g: f32 = rgb.green as f32 / 255.0;
-        let b: f32 = rgb.blue as f32 / 255.0;
-
-        let k: f32 = 1.0 - r.min(g).min(b);
-        let c: f32 = (1.0 - r - k) / (1.0 - k);
-        let m: f32 = (1.0 - g - k) / (1.0 - k);
-        let y: f32 = (1.0 - b - k) / (1.0 - k);
-
-        CMYK { cyan:    c,
-               magenta: m,
-               yellow:  y,
-               black:   k }
+        CMYK::from_rgb_f32(rgb.red as f32 / 255.0,
+                           rgb.green as f32 / 255.0,
+                           rgb.blue as f32 / 255.0)
     }
     /// Create a new `CMYK` struct from `RGB` values.
     /// # Arguments
@@ -95,6 +96,59 @@ impl CMYK {
     pub fn from_rgb_vals(red: u8, green: u8, blue: u8) -> CMYK {
         CMYK::from_rgba_struct(&RGBa::new(red, green, blue, 255))
     }
+    /// Create a new `CMYK` struct from an existing `HSV` struct.
+    /// # Arguments
+    /// * `hsv: &HSV` - The `HSV` struct.
+    /// # Returns
+    /// A new `CMYK` struct.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::{cmyk::CMYK, hsv::HSV};
+    ///
+    /// assert_eq!(CMYK::from_hsv_struct(&HSV::new(210.0, 0.5, 1.0)),
+    ///            CMYK::new(0.5, 0.25, 0.0, 0.0));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_hsv_struct(hsv: &HSV) -> CMYK {
+        let (r, g, b): (f32, f32, f32) = hsv.to_rgb_f32();
+        CMYK::from_rgb_f32(r, g, b)
+    }
+    /// Create a new `CMYK` struct from an existing `HSL` struct.
+    /// # Arguments
+    /// * `hsl: &HSL` - The `HSL` struct.
+    /// # Returns
+    /// A new `CMYK` struct.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::{cmyk::CMYK, hsl::HSL};
+    ///
+    /// assert_eq!(CMYK::from_hsl_struct(&HSL::SOLID_BLACK),
+    ///            CMYK::new(0.0, 0.0, 0.0, 1.0));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_hsl_struct(hsl: &HSL) -> CMYK {
+        let (r, g, b): (f32, f32, f32) = hsl.to_rgb_f32();
+        CMYK::from_rgb_f32(r, g, b)
+    }
+    /// Create a new `CMYK` struct from RGB parts in the interval `[0; 1]`. Black gets no cyan, magenta or yellow.
+    pub(super) fn from_rgb_f32(r: f32, g: f32, b: f32) -> CMYK {
+        let k: f32 = 1.0 - r.max(g).max(b);
+        if k == 1.0
+        { return CMYK { cyan: 0.0, magenta: 0.0, yellow: 0.0, black: 1.0 }; }
+
+        CMYK { cyan:    ((1.0 - r - k) / (1.0 - k)).clamp(0.0, 1.0),
+               magenta: ((1.0 - g - k) / (1.0 - k)).clamp(0.0, 1.0),
+               yellow:  ((1.0 - b - k) / (1.0 - k)).clamp(0.0, 1.0),
+               black:   k }
+    }
+    /// Get the RGB parts in the interval `[0; 1]`.
+    pub(super) fn to_rgb_f32(self) -> (f32, f32, f32) {
+        ((1.0 - self.cyan) * (1.0 - self.black),
+         (1.0 - self.magenta) * (1.0 - self.black),
+         (1.0 - self.yellow) * (1.0 - self.black))
+    }
     /// Get the reference to the `cyan` field.
     /// # Returns
     /// A `f32`.
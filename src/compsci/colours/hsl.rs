@@ -0,0 +1,330 @@
+//! In here you'll find the struct for the `HSL` colour-definition.
+use crate::{math::general::NumTools, compsci::colours::{cmyk::CMYK, hsv::{HSV, hue_of_rgb, normalise_hue}, rgba::RGBa}};
+
+const ARG_ERR: &str = "Saturation and lightness must range from 0 to 1.";
+/// A struct for storing HSL-Values: the hue in degrees, the saturation and the lightness.
+#[derive(Clone, Copy, PartialEq)]
+pub struct HSL {
+    hue:        f32,
+    saturation: f32,
+    lightness:  f32
+}
+
+impl HSL {
+    /// Create a new `HSL` struct.
+    /// # Arguments
+    /// * `hue: f32` - The hue in degrees. It is taken modulo 360, so `-90.0` becomes `270.0`.
+    /// * `saturation: f32` - The saturation.
+    /// * `lightness: f32` - The lightness.
+    /// # Returns
+    /// A new `HSL` struct.
+    /// # Panics
+    /// Panics if `hue` is not finite or if `saturation` or `lightness` is not in the interval `[0; 1]`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsl::HSL;
+    ///
+    /// let nice_blue = HSL::new(210.0, 1.0, 0.75);
+    /// assert_eq!(HSL::new(570.0, 1.0, 0.75), nice_blue);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new(hue:        f32,
+               saturation: f32,
+               lightness:  f32) -> HSL {
+        if !saturation.is_in_range(0.0, 1.0) ||
+           !lightness.is_in_range(0.0, 1.0)
+        { panic!("{}", ARG_ERR); }
+
+        HSL { hue: normalise_hue(hue), saturation, lightness }
+    }
+    /// Create a new `HSL` struct from an existing `RGBa` struct. The alpha part is dropped.
+    /// # Arguments
+    /// * `rgb: &RGBa` - The `RGBa` struct.
+    /// # Returns
+    /// A new `HSL` struct.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::{hsl::HSL, rgba::RGBa};
+    ///
+    /// let grey = HSL::from_rgba_struct(&RGBa::new(51, 51, 51, 255));
+    ///
+    /// assert_eq!(grey, HSL::new(0.0, 0.0, 0.2));
+    /// assert_eq!(RGBa::from_hsl_struct(&grey), RGBa::new(51, 51, 51, 255));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_rgba_struct(rgb: &RGBa) -> HSL {
+        HSL::from_rgb_f32(rgb.red as f32 / 255.0,
+                          rgb.green as f32 / 255.0,
+                          rgb.blue as f32 / 255.0)
+    }
+    /// Create a new `HSL` struct from `RGB` values.
+    /// # Arguments
+    /// * `red: u8` - The red part.
+    /// * `green: u8` - The green part.
+    /// * `blue: u8` - The blue part.
+    /// # Returns
+    /// A new `HSL` struct.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsl::HSL;
+    ///
+    /// assert_eq!(HSL::from_rgb_vals(0, 255, 0), HSL::SOLID_GREEN);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_rgb_vals(red: u8, green: u8, blue: u8) -> HSL {
+        HSL::from_rgba_struct(&RGBa::new(red, green, blue, 255))
+    }
+    /// Create a new `HSL` struct from an existing `CMYK` struct.
+    /// # Arguments
+    /// * `cmyk: &CMYK` - The `CMYK` struct.
+    /// # Returns
+    /// A new `HSL` struct.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::{cmyk::CMYK, hsl::HSL};
+    ///
+    /// let nice_blue = HSL::from_cmyk_struct(&CMYK::new(0.5, 0.25, 0.0, 0.0));
+    ///
+    /// assert_eq!(nice_blue, HSL::new(210.0, 1.0, 0.75));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_cmyk_struct(cmyk: &CMYK) -> HSL {
+        let (r, g, b): (f32, f32, f32) = cmyk.to_rgb_f32();
+        HSL::from_rgb_f32(r, g, b)
+    }
+    /// Create a new `HSL` struct from an existing `HSV` struct. The hue stays the same.
+    /// # Arguments
+    /// * `hsv: &HSV` - The `HSV` struct.
+    /// # Returns
+    /// A new `HSL` struct.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::{hsl::HSL, hsv::HSV};
+    ///
+    /// assert_eq!(HSL::from_hsv_struct(&HSV::new(210.0, 0.5, 1.0)), HSL::new(210.0, 1.0, 0.75));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_hsv_struct(hsv: &HSV) -> HSL {
+        let v: f32 = hsv.value();
+        let l: f32 = v * (1.0 - hsv.saturation() / 2.0);
+        let s: f32 = if l == 0.0 || l == 1.0 { 0.0 } else { (v - l) / l.min(1.0 - l) };
+
+        HSL { hue:        hsv.hue(),
+              saturation: s.clamp(0.0, 1.0),
+              lightness:  l.clamp(0.0, 1.0) }
+    }
+    /// Get the `hue` field, in degrees from `0` (inclusive) to `360` (exclusive).
+    /// # Returns
+    /// A `f32`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsl::HSL;
+    /// let nice_blue = HSL::new(210.0, 1.0, 0.75);
+    ///
+    /// assert_eq!(210.0, nice_blue.hue());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn hue(&self) -> f32 {
+        self.hue
+    }
+    /// Get the `saturation` field.
+    /// # Returns
+    /// A `f32`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsl::HSL;
+    /// let nice_blue = HSL::new(210.0, 1.0, 0.75);
+    ///
+    /// assert_eq!(1.0, nice_blue.saturation());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn saturation(&self) -> f32 {
+        self.saturation
+    }
+    /// Get the `lightness` field.
+    /// # Returns
+    /// A `f32`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsl::HSL;
+    /// let nice_blue = HSL::new(210.0, 1.0, 0.75);
+    ///
+    /// assert_eq!(0.75, nice_blue.lightness());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn lightness(&self) -> f32 {
+        self.lightness
+    }
+    /// Set the `hue` field.
+    /// # Arguments
+    /// * `degrees: f32` - The new hue in degrees. It is taken modulo 360.
+    /// # Panics
+    /// Panics if `degrees` is not finite.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsl::HSL;
+    /// let mut colour = HSL::new(210.0, 1.0, 0.75);
+    ///
+    /// colour.set_hue(-60.0);
+    /// assert_eq!(300.0, colour.hue());
+    /// ```
+    #[inline]
+    pub fn set_hue(&mut self, degrees: f32) {
+        self.hue = normalise_hue(degrees);
+    }
+    /// Set the `saturation` field.
+    /// # Arguments
+    /// * `value: f32` - The new value.
+    /// # Panics
+    /// Panics if `value` is not in the interval `[0; 1]`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsl::HSL;
+    /// let mut colour = HSL::new(210.0, 1.0, 0.75);
+    ///
+    /// colour.set_saturation(0.5);
+    /// assert_eq!(0.5, colour.saturation());
+    /// ```
+    #[inline]
+    pub fn set_saturation(&mut self, value: f32) {
+        if !value.is_in_range(0.0, 1.0)
+        { panic!("{}", ARG_ERR); }
+
+        self.saturation = value;
+    }
+    /// Set the `lightness` field.
+    /// # Arguments
+    /// * `value: f32` - The new value.
+    /// # Panics
+    /// Panics if `value` is not in the interval `[0; 1]`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsl::HSL;
+    /// let mut colour = HSL::new(210.0, 1.0, 0.75);
+    ///
+    /// colour.set_lightness(0.5);
+    /// assert_eq!(0.5, colour.lightness());
+    /// ```
+    #[inline]
+    pub fn set_lightness(&mut self, value: f32) {
+        if !value.is_in_range(0.0, 1.0)
+        { panic!("{}", ARG_ERR); }
+
+        self.lightness = value;
+    }
+    /// Rotates the hue around the colour wheel.
+    /// # Arguments
+    /// * `degrees: f32` - The angle to rotate by. Negative angles rotate backwards.
+    /// # Panics
+    /// Panics if `degrees` is not finite.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::{hsl::HSL, rgba::RGBa};
+    /// let mut colour = HSL::SOLID_RED;
+    ///
+    /// colour.rotate_hue(-120.0);
+    /// assert_eq!(RGBa::from_hsl_struct(&colour), RGBa::SOLID_BLUE);
+    /// ```
+    #[inline]
+    pub fn rotate_hue(&mut self, degrees: f32) {
+        self.set_hue(self.hue + degrees);
+    }
+    /// Changes the saturation by `amount`, staying in the interval `[0; 1]`.
+    /// # Arguments
+    /// * `amount: f32` - The amount to add. Negative amounts desaturate.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsl::HSL;
+    /// let mut colour = HSL::new(210.0, 1.0, 0.75);
+    ///
+    /// colour.saturate(-0.25);
+    /// assert_eq!(0.75, colour.saturation());
+    /// ```
+    #[inline]
+    pub fn saturate(&mut self, amount: f32) {
+        self.saturation = (self.saturation + amount).clamp(0.0, 1.0);
+    }
+    /// Changes the lightness by `amount`, staying in the interval `[0; 1]`.
+    /// # Arguments
+    /// * `amount: f32` - The amount to add. Negative amounts darken.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsl::HSL;
+    /// let mut colour = HSL::new(210.0, 1.0, 0.75);
+    ///
+    /// colour.lighten(0.5);
+    /// assert_eq!(1.0, colour.lightness());
+    /// ```
+    #[inline]
+    pub fn lighten(&mut self, amount: f32) {
+        self.lightness = (self.lightness + amount).clamp(0.0, 1.0);
+    }
+    /// Create a new `HSL` struct from RGB parts in the interval `[0; 1]`.
+    pub(super) fn from_rgb_f32(r: f32, g: f32, b: f32) -> HSL {
+        let max: f32 = r.max(g).max(b);
+        let min: f32 = r.min(g).min(b);
+        let l:   f32 = (max + min) / 2.0;
+        let s:   f32 = if max == min { 0.0 } else { (max - min) / (1.0 - (2.0 * l - 1.0).abs()) };
+
+        HSL { hue:        hue_of_rgb(r, g, b),
+              saturation: s.clamp(0.0, 1.0),
+              lightness:  l }
+    }
+    /// Get the RGB parts in the interval `[0; 1]`.
+    pub(super) fn to_rgb_f32(self) -> (f32, f32, f32) {
+        let a: f32 = self.saturation * self.lightness.min(1.0 - self.lightness);
+        let f = |n: f32| -> f32 {
+            let k: f32 = (n + self.hue / 30.0) % 12.0;
+            self.lightness - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+        };
+        (f(0.0), f(8.0), f(4.0))
+    }
+    /// The pure colour red, Hex-Code `FF0000`.
+    pub const SOLID_RED: HSL = HSL { hue: 0.0, saturation: 1.0, lightness: 0.5 };
+    /// The pure colour green, Hex-Code `00FF00`.
+    pub const SOLID_GREEN: HSL = HSL { hue: 120.0, saturation: 1.0, lightness: 0.5 };
+    /// The pure colour blue, Hex-Code `0000FF`.
+    pub const SOLID_BLUE: HSL = HSL { hue: 240.0, saturation: 1.0, lightness: 0.5 };
+    /// The pure colour white, Hex-Code `FFFFFF`.
+    pub const SOLID_WHITE: HSL = HSL { hue: 0.0, saturation: 0.0, lightness: 1.0 };
+    /// The pure colour black, Hex-Code `000000`.
+    pub const SOLID_BLACK: HSL = HSL { hue: 0.0, saturation: 0.0, lightness: 0.0 };
+    /// LibRapid Blue, Hex-Code `9074FF`.
+    pub const LIBRAPID_SOLID_BLUE: HSL = HSL { hue: 252.086_33, saturation: 1.0, lightness: 0.727_450_97 };
+    /// LibRapid Red, Hex-Code `FF3600`.
+    pub const LIBRAPID_SOLID_RED: HSL = HSL { hue: 12.705_882, saturation: 1.0, lightness: 0.5 };
+}
+
+impl std::fmt::Display for HSL {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}°; {}; {})", self.hue(),
+                                   self.saturation(),
+                                   self.lightness())
+    }
+}
+
+impl std::fmt::Debug for HSL {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(H: {}°; S: {}; L: {})", self.hue(),
+                                            self.saturation(),
+                                            self.lightness())
+    }
+}
+/// Iterate over a `HSL` struct in the order `H, S, L`.
+impl IntoIterator for HSL {
+    type Item = f32;
+
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        vec![self.hue(), self.saturation(), self.lightness()].into_iter()
+    }
+}
\ No newline at end of file
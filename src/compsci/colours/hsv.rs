@@ -0,0 +1,350 @@
+//! In here you'll find the struct for the `HSV` colour-definition, also known as `HSB`.
+use crate::{math::general::NumTools, compsci::colours::{cmyk::CMYK, hsl::HSL, rgba::RGBa}};
+
+const ARG_ERR: &str = "Saturation and value must range from 0 to 1.";
+/// A struct for storing HSV-Values: the hue in degrees, the saturation and the value (brightness).
+#[derive(Clone, Copy, PartialEq)]
+pub struct HSV {
+    hue:        f32,
+    saturation: f32,
+    value:      f32
+}
+
+impl HSV {
+    /// Create a new `HSV` struct.
+    /// # Arguments
+    /// * `hue: f32` - The hue in degrees. It is taken modulo 360, so `-90.0` becomes `270.0`.
+    /// * `saturation: f32` - The saturation.
+    /// * `value: f32` - The value.
+    /// # Returns
+    /// A new `HSV` struct.
+    /// # Panics
+    /// Panics if `hue` is not finite or if `saturation` or `value` is not in the interval `[0; 1]`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsv::HSV;
+    ///
+    /// let nice_blue = HSV::new(210.0, 0.5, 1.0);
+    /// assert_eq!(HSV::new(-150.0, 0.5, 1.0), nice_blue);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new(hue:        f32,
+               saturation: f32,
+               value:      f32) -> HSV {
+        if !saturation.is_in_range(0.0, 1.0) ||
+           !value.is_in_range(0.0, 1.0)
+        { panic!("{}", ARG_ERR); }
+
+        HSV { hue: normalise_hue(hue), saturation, value }
+    }
+    /// Create a new `HSV` struct from an existing `RGBa` struct. The alpha part is dropped.
+    /// # Arguments
+    /// * `rgb: &RGBa` - The `RGBa` struct.
+    /// # Returns
+    /// A new `HSV` struct.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::{hsv::HSV, rgba::RGBa};
+    ///
+    /// let nice_blue = HSV::from_rgba_struct(&RGBa::new(128, 191, 255, 255));
+    ///
+    /// assert_eq!(nice_blue.value(), 1.0);
+    /// assert_eq!(RGBa::from_hsv_struct(&nice_blue), RGBa::new(128, 191, 255, 255));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_rgba_struct(rgb: &RGBa) -> HSV {
+        HSV::from_rgb_f32(rgb.red as f32 / 255.0,
+                          rgb.green as f32 / 255.0,
+                          rgb.blue as f32 / 255.0)
+    }
+    /// Create a new `HSV` struct from `RGB` values.
+    /// # Arguments
+    /// * `red: u8` - The red part.
+    /// * `green: u8` - The green part.
+    /// * `blue: u8` - The blue part.
+    /// # Returns
+    /// A new `HSV` struct.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsv::HSV;
+    ///
+    /// assert_eq!(HSV::from_rgb_vals(255, 0, 0), HSV::SOLID_RED);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_rgb_vals(red: u8, green: u8, blue: u8) -> HSV {
+        HSV::from_rgba_struct(&RGBa::new(red, green, blue, 255))
+    }
+    /// Create a new `HSV` struct from an existing `CMYK` struct.
+    /// # Arguments
+    /// * `cmyk: &CMYK` - The `CMYK` struct.
+    /// # Returns
+    /// A new `HSV` struct.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::{cmyk::CMYK, hsv::HSV};
+    ///
+    /// let nice_blue = HSV::from_cmyk_struct(&CMYK::new(0.5, 0.25, 0.0, 0.0));
+    ///
+    /// assert_eq!(nice_blue, HSV::new(210.0, 0.5, 1.0));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_cmyk_struct(cmyk: &CMYK) -> HSV {
+        let (r, g, b): (f32, f32, f32) = cmyk.to_rgb_f32();
+        HSV::from_rgb_f32(r, g, b)
+    }
+    /// Create a new `HSV` struct from an existing `HSL` struct. The hue stays the same.
+    /// # Arguments
+    /// * `hsl: &HSL` - The `HSL` struct.
+    /// # Returns
+    /// A new `HSV` struct.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::{hsl::HSL, hsv::HSV};
+    ///
+    /// assert_eq!(HSV::from_hsl_struct(&HSL::new(210.0, 1.0, 0.75)), HSV::new(210.0, 0.5, 1.0));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_hsl_struct(hsl: &HSL) -> HSV {
+        let l: f32 = hsl.lightness();
+        let v: f32 = l + hsl.saturation() * l.min(1.0 - l);
+        let s: f32 = if v == 0.0 { 0.0 } else { 2.0 * (1.0 - l / v) };
+
+        HSV { hue:        hsl.hue(),
+              saturation: s.clamp(0.0, 1.0),
+              value:      v.clamp(0.0, 1.0) }
+    }
+    /// Get the `hue` field, in degrees from `0` (inclusive) to `360` (exclusive).
+    /// # Returns
+    /// A `f32`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsv::HSV;
+    /// let nice_blue = HSV::new(210.0, 0.5, 1.0);
+    ///
+    /// assert_eq!(210.0, nice_blue.hue());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn hue(&self) -> f32 {
+        self.hue
+    }
+    /// Get the `saturation` field.
+    /// # Returns
+    /// A `f32`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsv::HSV;
+    /// let nice_blue = HSV::new(210.0, 0.5, 1.0);
+    ///
+    /// assert_eq!(0.5, nice_blue.saturation());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn saturation(&self) -> f32 {
+        self.saturation
+    }
+    /// Get the `value` field.
+    /// # Returns
+    /// A `f32`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsv::HSV;
+    /// let nice_blue = HSV::new(210.0, 0.5, 1.0);
+    ///
+    /// assert_eq!(1.0, nice_blue.value());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn value(&self) -> f32 {
+        self.value
+    }
+    /// Set the `hue` field.
+    /// # Arguments
+    /// * `degrees: f32` - The new hue in degrees. It is taken modulo 360.
+    /// # Panics
+    /// Panics if `degrees` is not finite.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsv::HSV;
+    /// let mut colour = HSV::new(210.0, 0.5, 1.0);
+    ///
+    /// colour.set_hue(480.0);
+    /// assert_eq!(120.0, colour.hue());
+    /// ```
+    #[inline]
+    pub fn set_hue(&mut self, degrees: f32) {
+        self.hue = normalise_hue(degrees);
+    }
+    /// Set the `saturation` field.
+    /// # Arguments
+    /// * `value: f32` - The new value.
+    /// # Panics
+    /// Panics if `value` is not in the interval `[0; 1]`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsv::HSV;
+    /// let mut colour = HSV::new(210.0, 0.5, 1.0);
+    ///
+    /// colour.set_saturation(1.0);
+    /// assert_eq!(1.0, colour.saturation());
+    /// ```
+    #[inline]
+    pub fn set_saturation(&mut self, value: f32) {
+        if !value.is_in_range(0.0, 1.0)
+        { panic!("{}", ARG_ERR); }
+
+        self.saturation = value;
+    }
+    /// Set the `value` field.
+    /// # Arguments
+    /// * `value: f32` - The new value.
+    /// # Panics
+    /// Panics if `value` is not in the interval `[0; 1]`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsv::HSV;
+    /// let mut colour = HSV::new(210.0, 0.5, 1.0);
+    ///
+    /// colour.set_value(0.25);
+    /// assert_eq!(0.25, colour.value());
+    /// ```
+    #[inline]
+    pub fn set_value(&mut self, value: f32) {
+        if !value.is_in_range(0.0, 1.0)
+        { panic!("{}", ARG_ERR); }
+
+        self.value = value;
+    }
+    /// Rotates the hue around the colour wheel.
+    /// # Arguments
+    /// * `degrees: f32` - The angle to rotate by. Negative angles rotate backwards.
+    /// # Panics
+    /// Panics if `degrees` is not finite.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsv::HSV;
+    /// let mut colour = HSV::SOLID_BLUE;
+    ///
+    /// colour.rotate_hue(180.0);
+    /// assert_eq!(60.0, colour.hue());
+    /// ```
+    #[inline]
+    pub fn rotate_hue(&mut self, degrees: f32) {
+        self.set_hue(self.hue + degrees);
+    }
+    /// Changes the saturation by `amount`, staying in the interval `[0; 1]`.
+    /// # Arguments
+    /// * `amount: f32` - The amount to add. Negative amounts desaturate.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsv::HSV;
+    /// let mut colour = HSV::new(210.0, 0.5, 1.0);
+    ///
+    /// colour.saturate(0.75);
+    /// assert_eq!(1.0, colour.saturation());
+    /// ```
+    #[inline]
+    pub fn saturate(&mut self, amount: f32) {
+        self.saturation = (self.saturation + amount).clamp(0.0, 1.0);
+    }
+    /// Changes the value by `amount`, staying in the interval `[0; 1]`.
+    /// # Arguments
+    /// * `amount: f32` - The amount to add. Negative amounts darken.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::hsv::HSV;
+    /// let mut colour = HSV::new(210.0, 0.5, 1.0);
+    ///
+    /// colour.brighten(-0.25);
+    /// assert_eq!(0.75, colour.value());
+    /// ```
+    #[inline]
+    pub fn brighten(&mut self, amount: f32) {
+        self.value = (self.value + amount).clamp(0.0, 1.0);
+    }
+    /// Create a new `HSV` struct from RGB parts in the interval `[0; 1]`.
+    pub(super) fn from_rgb_f32(r: f32, g: f32, b: f32) -> HSV {
+        let max: f32 = r.max(g).max(b);
+        let min: f32 = r.min(g).min(b);
+
+        HSV { hue:        hue_of_rgb(r, g, b),
+              saturation: if max == 0.0 { 0.0 } else { (max - min) / max },
+              value:      max }
+    }
+    /// Get the RGB parts in the interval `[0; 1]`.
+    pub(super) fn to_rgb_f32(self) -> (f32, f32, f32) {
+        let f = |n: f32| -> f32 {
+            let k: f32 = (n + self.hue / 60.0) % 6.0;
+            self.value - self.value * self.saturation * k.min(4.0 - k).clamp(0.0, 1.0)
+        };
+        (f(5.0), f(3.0), f(1.0))
+    }
+    /// The pure colour red, Hex-Code `FF0000`.
+    pub const SOLID_RED: HSV = HSV { hue: 0.0, saturation: 1.0, value: 1.0 };
+    /// The pure colour green, Hex-Code `00FF00`.
+    pub const SOLID_GREEN: HSV = HSV { hue: 120.0, saturation: 1.0, value: 1.0 };
+    /// The pure colour blue, Hex-Code `0000FF`.
+    pub const SOLID_BLUE: HSV = HSV { hue: 240.0, saturation: 1.0, value: 1.0 };
+    /// The pure colour white, Hex-Code `FFFFFF`.
+    pub const SOLID_WHITE: HSV = HSV { hue: 0.0, saturation: 0.0, value: 1.0 };
+    /// The pure colour black, Hex-Code `000000`.
+    pub const SOLID_BLACK: HSV = HSV { hue: 0.0, saturation: 0.0, value: 0.0 };
+    /// LibRapid Blue, Hex-Code `9074FF`.
+    pub const LIBRAPID_SOLID_BLUE: HSV = HSV { hue: 252.086_33, saturation: 0.545_098_07, value: 1.0 };
+    /// LibRapid Red, Hex-Code `FF3600`.
+    pub const LIBRAPID_SOLID_RED: HSV = HSV { hue: 12.705_882, saturation: 1.0, value: 1.0 };
+}
+
+/// Get the hue in degrees of RGB parts in the interval `[0; 1]`. Greys get the hue `0`.
+pub(super) fn hue_of_rgb(r: f32, g: f32, b: f32) -> f32 {
+    let max:   f32 = r.max(g).max(b);
+    let delta: f32 = max - r.min(g).min(b);
+    if delta == 0.0
+    { return 0.0; }
+
+    let sector: f32 = if max == r      { (g - b) / delta }
+                      else if max == g { (b - r) / delta + 2.0 }
+                      else             { (r - g) / delta + 4.0 };
+    normalise_hue(60.0 * sector)
+}
+
+/// Maps any finite angle onto the interval `[0; 360)`.
+pub(super) fn normalise_hue(degrees: f32) -> f32 {
+    if !degrees.is_finite()
+    { panic!("The hue must be finite."); }
+
+    let res: f32 = degrees.rem_euclid(360.0);
+    // Tiny negative angles round up to exactly 360.
+    if res >= 360.0 { 0.0 } else { res }
+}
+
+impl std::fmt::Display for HSV {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}°; {}; {})", self.hue(),
+                                   self.saturation(),
+                                   self.value())
+    }
+}
+
+impl std::fmt::Debug for HSV {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(H: {}°; S: {}; V: {})", self.hue(),
+                                            self.saturation(),
+                                            self.value())
+    }
+}
+/// Iterate over a `HSV` struct in the order `H, S, V`.
+impl IntoIterator for HSV {
+    type Item = f32;
+
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        vec![self.hue(), self.saturation(), self.value()].into_iter()
+    }
+}
\ No newline at end of file
@@ -1,5 +1,5 @@
 //! In here you'll find the struct for the classic `RGB` colour-definition and the extended `RGBa`-definition.
-use crate::{compsci::colours::cmyk::CMYK, math::general::Averages};
+use crate::{compsci::colours::{cmyk::CMYK, hsl::HSL, hsv::HSV}, math::general::Averages};
 /// A struct for storing RGBa-Values.
 #[derive(Clone, Copy, PartialEq)]
 pub struct RGBa {
@@ -88,6 +88,49 @@ impl RGBa {
                blue:  (255.0 * (1.0 - cmyk.yellow()) * (1.0 - cmyk.black())).round() as u8,
                alpha: 255 }
     }
+    /// Create a new opaque `RGBa` struct from an existing `HSV` struct.
+    /// # Arguments
+    /// * `hsv: &HSV` - The `HSV` struct.
+    /// # Returns
+    /// A new `RGBa` struct.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::{hsv::HSV, rgba::RGBa};
+    ///
+    /// assert_eq!(RGBa::from_hsv_struct(&HSV::new(210.0, 0.5, 1.0)),
+    ///            RGBa::new(128, 191, 255, 255));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_hsv_struct(hsv: &HSV) -> RGBa {
+        let (r, g, b): (f32, f32, f32) = hsv.to_rgb_f32();
+        RGBa::from_rgb_f32(r, g, b)
+    }
+    /// Create a new opaque `RGBa` struct from an existing `HSL` struct.
+    /// # Arguments
+    /// * `hsl: &HSL` - The `HSL` struct.
+    /// # Returns
+    /// A new `RGBa` struct.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::colours::{hsl::HSL, rgba::RGBa};
+    ///
+    /// assert_eq!(RGBa::from_hsl_struct(&HSL::LIBRAPID_SOLID_BLUE),
+    ///            RGBa::LIBRAPID_SOLID_BLUE);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_hsl_struct(hsl: &HSL) -> RGBa {
+        let (r, g, b): (f32, f32, f32) = hsl.to_rgb_f32();
+        RGBa::from_rgb_f32(r, g, b)
+    }
+    /// Create a new opaque `RGBa` struct from RGB parts in the interval `[0; 1]`.
+    pub(super) fn from_rgb_f32(r: f32, g: f32, b: f32) -> RGBa {
+        RGBa { red:   (255.0 * r).round() as u8,
+               green: (255.0 * g).round() as u8,
+               blue:  (255.0 * b).round() as u8,
+               alpha: 255 }
+    }
     /// Determines whethe `self` is transparent or not. `true` if `alpha != 255`, otherwise `false`.
     /// # Returns
     /// A `bool`.
@@ -16,4 +16,25 @@
 /// Huffman invented this algorithm for text compression, but this is now the base of many more compression methods.
 /// He proved that this was the mathematically most efficient way of assigning bits to chars (can also be used for strings).
 /// It works by calculating the overall probablity for each character to appear. The one with the highest probability gets the lowest value etc.
-pub mod huffman;
\ No newline at end of file
+pub mod huffman;
+/// Implements the LZSS-compression algorithm, a sliding-window dictionary coder for byte slices.
+/// # Efficiency
+/// LZSS shines on data with long repetitions, which Huffman coding alone can't exploit.
+/// `lzss::compress_huffman` chains both for a general-purpose compressor.
+pub mod lzss;
+/// Implements DEFLATE with the zlib and gzip containers, which are compatible with all common tools.
+/// # Efficiency
+/// DEFLATE combines LZSS-style matching with Huffman coding and is the format behind `.gz`, `.zip` and `.png` files.
+pub mod deflate;
+/// Implements a block-sorting compressor in the style of bzip2: Burrows-Wheeler transform, move-to-front coding, run-length encoding and Huffman coding.
+/// # Efficiency
+/// Block sorting groups bytes by their context, which pays off on text with many similar passages such as genomic data.
+pub mod bwt;
+/// Implements a range coder with static, adaptive and context models.
+/// # Efficiency
+/// Range coding spends fractions of bits per symbol, so it beats Huffman coding on skewed distributions.
+pub mod range_coder;
+/// Implements the LZW-compression algorithm with variable code widths, as used by GIF images and `.Z` files.
+pub mod lzw;
+/// Tools for measuring data before choosing a codec: entropy, expected Huffman code lengths and compression reports.
+pub mod analysis;
\ No newline at end of file
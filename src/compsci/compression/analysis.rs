@@ -0,0 +1,320 @@
+//! Measures data before choosing a codec: Shannon entropy, the expected length of Huffman codes and compression reports.
+//! All entropies and code lengths are in bits per symbol. Symbols are characters for `&str` and `String`, bytes otherwise.
+use core::fmt;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::huffman::{Node, MAX_CODE_LENGTH, try_get_root, try_get_root_from_frequencies, limited_code_lengths, get_frequency, get_frequency_of};
+
+/// The sizes of some data before and after compression, in bytes.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::analysis::CompressionReport;
+/// let report = CompressionReport::new(1000, 400, 40);
+///
+/// assert_eq!(report.ratio(), 0.4);
+/// assert_eq!(report.space_savings(), 0.6);
+/// assert_eq!(report.payload_size(), 360);
+/// assert_eq!(report.to_string(), "1000 B -> 400 B (40.00 %, 40 B header)");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CompressionReport {
+    /// The size of the uncompressed data.
+    pub original_size:   usize,
+    /// The size of the compressed data, including all headers.
+    pub compressed_size: usize,
+    /// The part of `compressed_size` spent on headers, code tables, checksums and the like.
+    pub header_overhead: usize,
+}
+
+impl CompressionReport {
+    /// Creates a new `CompressionReport`, e.g. out of the output of any codec.
+    /// # Arguments
+    /// * `original_size` - The size of the uncompressed data.
+    /// * `compressed_size` - The size of the compressed data.
+    /// * `header_overhead` - The part of `compressed_size` which isn't payload.
+    /// # Returns
+    /// A new `CompressionReport`.
+    /// # Panics
+    /// Panics if `header_overhead` is greater than `compressed_size`.
+    #[must_use]
+    pub const fn new(original_size: usize, compressed_size: usize, header_overhead: usize) -> CompressionReport {
+        if header_overhead > compressed_size
+        { panic!("The header overhead cannot be greater than the compressed size."); }
+        CompressionReport { original_size, compressed_size, header_overhead }
+    }
+    /// Get the compressed size relative to the original size. Smaller is better.
+    /// # Returns
+    /// A `f64`, which is `1.0` for empty data.
+    #[must_use]
+    pub fn ratio(&self) -> f64 {
+        if self.original_size == 0
+        { return 1.0; }
+        self.compressed_size as f64 / self.original_size as f64
+    }
+    /// Get the share of the original size which compression saves. Negative if the data grew.
+    /// # Returns
+    /// A `f64`.
+    #[must_use]
+    pub fn space_savings(&self) -> f64 {
+        1.0 - self.ratio()
+    }
+    /// Get the size of the compressed data without headers.
+    #[must_use]
+    pub const fn payload_size(&self) -> usize {
+        self.compressed_size - self.header_overhead
+    }
+}
+
+impl fmt::Display for CompressionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} B -> {} B ({:.2} %, {} B header)",
+               self.original_size, self.compressed_size, self.ratio() * 100.0, self.header_overhead)
+    }
+}
+
+/// Calculates the order-0 Shannon entropy out of symbol frequencies.
+/// # Arguments
+/// * `frequency` - How often each symbol occurs.
+/// # Returns
+/// A `f64` with the entropy in bits per symbol, `0.0` for no symbols.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::analysis::entropy_from_frequencies;
+/// use std::collections::HashMap;
+/// let frequency: HashMap<char, usize> = vec![('a', 2), ('b', 1), ('c', 1)].into_iter().collect();
+///
+/// assert_eq!(entropy_from_frequencies(&frequency), 1.5);
+/// ```
+#[must_use]
+pub fn entropy_from_frequencies<T>(frequency: &HashMap<T, usize>) -> f64 {
+    let total: f64 = frequency.values().sum::<usize>() as f64;
+    frequency.values()
+             .filter(|f| **f > 0)
+             .map(|f| *f as f64 / total * (total / *f as f64).log2())
+             .sum()
+}
+
+/// Calculates the average length of the Huffman codes `get_root` would assign, weighted by the symbol frequencies.
+/// # Arguments
+/// * `frequency` - How often each symbol occurs.
+/// # Returns
+/// A `f64` with the expected code length in bits per symbol, `0.0` for no symbols.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::analysis::expected_length_from_frequencies;
+/// use std::collections::HashMap;
+/// let frequency: HashMap<char, usize> = vec![('a', 2), ('b', 1), ('c', 1)].into_iter().collect();
+///
+/// // 'a' gets a 1 bit code, 'b' and 'c' get 2 bit codes.
+/// assert_eq!(expected_length_from_frequencies(&frequency), 1.5);
+/// ```
+#[must_use]
+pub fn expected_length_from_frequencies<T: Copy>(frequency: &HashMap<T, usize>) -> f64 {
+    match try_get_root_from_frequencies(frequency) {
+        Ok(root) => expected_length_of(&root),
+        Err(_)   => 0.0,
+    }
+}
+
+/// Calculates the order-k entropy of a symbol slice, i.e. the entropy of a symbol given the `k` symbols before it.
+/// This is what a context model of order `k` could achieve at best. Order 0 is the plain Shannon entropy.
+/// # Arguments
+/// * `symbols` - The symbols.
+/// * `k` - The length of the context.
+/// # Returns
+/// A `f64` with the entropy in bits per symbol, `0.0` if there are no more than `k` symbols.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::analysis::entropy_order_k_of;
+/// let symbols = b"abababababababab";
+///
+/// assert_eq!(entropy_order_k_of(symbols, 0), 1.0);
+/// // Once the previous byte is known, the next one is certain.
+/// assert_eq!(entropy_order_k_of(symbols, 1), 0.0);
+/// ```
+#[must_use]
+pub fn entropy_order_k_of<T: Hash + Eq + Copy>(symbols: &[T], k: usize) -> f64 {
+    if k == 0
+    { return entropy_from_frequencies(&get_frequency_of(symbols)); }
+    if symbols.len() <= k
+    { return 0.0; }
+
+    let mut contexts: HashMap<&[T], HashMap<T, usize>> = HashMap::new();
+    for w in symbols.windows(k + 1) {
+        *contexts.entry(&w[..k])
+                 .or_default()
+                 .entry(w[k])
+                 .or_insert(0) += 1;
+    }
+
+    let total: f64 = (symbols.len() - k) as f64;
+    contexts.values()
+            .map(|f| f.values().sum::<usize>() as f64 / total * entropy_from_frequencies(f))
+            .sum()
+}
+
+/// The frequency-weighted average depth of the leaves of a Huffman tree.
+fn expected_length_of<T>(root: &Node<T>) -> f64 {
+    fn weighted_depth<T>(node: &Node<T>, depth: u128) -> u128 {
+        match (&node.left, &node.right) {
+            (None, None) => node.frequency * depth,
+            (l, r)       => l.as_ref().map_or(0, |x| weighted_depth(x, depth + 1))
+                          + r.as_ref().map_or(0, |x| weighted_depth(x, depth + 1)),
+        }
+    }
+    if root.frequency == 0
+    { return 0.0; }
+    weighted_depth(root, 0) as f64 / root.frequency as f64
+}
+
+/// Predicts the output of `huffman::stream::HuffmanWriter` for a single block holding all of `bytes`, without encoding.
+fn huffman_stream_report(bytes: &[u8]) -> CompressionReport {
+    // Magic number, version and end marker.
+    let framing: usize = 9;
+    if bytes.is_empty()
+    { return CompressionReport::new(0, framing, framing); }
+
+    let frequency: HashMap<u8, usize> = get_frequency_of(bytes);
+    // Can't fail: there is at least one symbol and 256 symbols always fit into codes of `MAX_CODE_LENGTH` bits.
+    let lengths:   Vec<(u8, u8)>      = limited_code_lengths(&frequency, MAX_CODE_LENGTH).unwrap();
+    let bits:      usize              = lengths.iter().map(|(s, l)| frequency[s] * *l as usize).sum();
+    // Length, table size, table, number of bits and checksum of the block.
+    let header:    usize              = framing + 4 + 2 + 2 * lengths.len() + 4 + 4;
+    CompressionReport::new(bytes.len(), header + bits.div_ceil(8), header)
+}
+
+/// Analysis trait for minimal code writing, implemented for Strings and byte slices.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::analysis::Analyze;
+/// let text = "the quick brown fox jumps over the lazy dog. ".repeat(20);
+///
+/// // Huffman codes are never shorter than the entropy, and less than one bit longer.
+/// assert!(text.redundancy() >= 0.0 && text.redundancy() < 1.0);
+/// // Knowing the previous characters makes the next one far more predictable.
+/// assert!(text.entropy_order_k(2) < text.entropy() / 4.0);
+/// ```
+pub trait Analyze {
+    /// Calculates the order-0 Shannon entropy, the lower bound for coding every symbol on its own.
+    /// # Returns
+    /// A `f64` with the entropy in bits per symbol.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::compression::analysis::Analyze;
+    /// assert_eq!("aaaa".entropy(), 0.0);
+    /// assert_eq!("abcd".entropy(), 2.0);
+    /// assert_eq!([0u8, 1, 2, 3, 4, 5, 6, 7].as_slice().entropy(), 3.0);
+    /// ```
+    #[must_use]
+    fn entropy(&self) -> f64;
+    /// Calculates the order-k entropy, i.e. the entropy of a symbol given the `k` symbols before it.
+    /// # Arguments
+    /// * `k` - The length of the context.
+    /// # Returns
+    /// A `f64` with the entropy in bits per symbol.
+    #[must_use]
+    fn entropy_order_k(&self, k: usize) -> f64;
+    /// Calculates the average length of the Huffman codes `get_root` would assign.
+    /// # Returns
+    /// A `f64` with the expected code length in bits per symbol.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::compression::analysis::Analyze;
+    /// // A single symbol still needs one bit.
+    /// assert_eq!("aaaa".expected_code_length(), 1.0);
+    /// assert_eq!("aabc".expected_code_length(), 1.5);
+    /// ```
+    #[must_use]
+    fn expected_code_length(&self) -> f64;
+    /// Calculates the redundancy of Huffman coding, the expected code length minus the entropy.
+    /// A high redundancy means a range coder would do noticeably better.
+    /// # Returns
+    /// A `f64` in bits per symbol.
+    #[must_use]
+    fn redundancy(&self) -> f64 {
+        self.expected_code_length() - self.entropy()
+    }
+    /// Predicts the result of compressing the (UTF-8) bytes with `huffman::stream::HuffmanWriter` in a single block.
+    /// # Returns
+    /// A `CompressionReport`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::compression::analysis::Analyze;
+    /// use lib_rapid::compsci::compression::huffman::stream::HuffmanWriter;
+    /// use std::io::Write;
+    /// let data: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit.";
+    ///
+    /// let mut writer = HuffmanWriter::with_block_size(Vec::new(), data.len());
+    /// writer.write_all(data).unwrap();
+    /// let report = data.huffman_report();
+    ///
+    /// assert_eq!(report.compressed_size, writer.finish().unwrap().len());
+    /// assert!(report.header_overhead > report.payload_size()); // Too short to pay off.
+    /// ```
+    #[must_use]
+    fn huffman_report(&self) -> CompressionReport;
+}
+
+impl Analyze for &str {
+    fn entropy(&self) -> f64 {
+        entropy_from_frequencies(&get_frequency(self))
+    }
+    fn entropy_order_k(&self, k: usize) -> f64 {
+        entropy_order_k_of(&self.chars().collect::<Vec<char>>(), k)
+    }
+    fn expected_code_length(&self) -> f64 {
+        match try_get_root(self) {
+            Ok(root) => expected_length_of(&root),
+            Err(_)   => 0.0,
+        }
+    }
+    fn huffman_report(&self) -> CompressionReport {
+        huffman_stream_report(self.as_bytes())
+    }
+}
+
+impl Analyze for String {
+    fn entropy(&self) -> f64 {
+        self.as_str().entropy()
+    }
+    fn entropy_order_k(&self, k: usize) -> f64 {
+        self.as_str().entropy_order_k(k)
+    }
+    fn expected_code_length(&self) -> f64 {
+        self.as_str().expected_code_length()
+    }
+    fn huffman_report(&self) -> CompressionReport {
+        self.as_str().huffman_report()
+    }
+}
+
+impl Analyze for &[u8] {
+    fn entropy(&self) -> f64 {
+        entropy_from_frequencies(&get_frequency_of(self))
+    }
+    fn entropy_order_k(&self, k: usize) -> f64 {
+        entropy_order_k_of(self, k)
+    }
+    fn expected_code_length(&self) -> f64 {
+        expected_length_from_frequencies(&get_frequency_of(self))
+    }
+    fn huffman_report(&self) -> CompressionReport {
+        huffman_stream_report(self)
+    }
+}
+
+impl Analyze for Vec<u8> {
+    fn entropy(&self) -> f64 {
+        self.as_slice().entropy()
+    }
+    fn entropy_order_k(&self, k: usize) -> f64 {
+        self.as_slice().entropy_order_k(k)
+    }
+    fn expected_code_length(&self) -> f64 {
+        self.as_slice().expected_code_length()
+    }
+    fn huffman_report(&self) -> CompressionReport {
+        self.as_slice().huffman_report()
+    }
+}
\ No newline at end of file
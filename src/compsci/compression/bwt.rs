@@ -0,0 +1,400 @@
+//! A block-sorting compressor in the style of bzip2.
+//! Every stage is usable on its own:
+//! 1. The Burrows-Wheeler transform (`bwt`) sorts all rotations of a block, which groups bytes with similar contexts.
+//! 2. Move-to-front coding (`mtf_encode`) turns these groups into runs of small numbers, mostly zeros.
+//! 3. Run-length encoding (`rle_encode`) shortens long runs of the same byte.
+//! 4. The streaming Huffman coder removes the remaining redundancy.
+//! # Format
+//! All integers are little endian.
+//! * `[u8; 4]` - The magic number `LRBW`.
+//! * `u8` - The format version.
+//! * Any number of blocks:
+//!     * `u32` - The number of bytes in the block. `0` marks the end of the data.
+//!     * `u32` - The primary index of the transform.
+//!     * A Huffman stream (see `huffman::stream`) of the transformed block.
+use core::fmt;
+use std::io::{Read, Write};
+
+use super::huffman::{HuffmanError, stream::{HuffmanWriter, HuffmanReader}};
+
+/// The magic number every LibRapid block-sorting stream starts with.
+pub const BWT_MAGIC: [u8; 4] = *b"LRBW";
+/// The version of the format written by `compress`.
+pub const BWT_VERSION: u8 = 1;
+/// The block size bzip2 uses at its highest level.
+pub const DEFAULT_BLOCK_SIZE: usize = 900_000;
+/// The number of equal bytes after which `rle_encode` writes a count.
+const RUN_START: usize = 4;
+
+/// The errors that can occur while undoing the block-sorting pipeline.
+#[derive(Debug)]
+pub enum BwtError {
+    /// The header is malformed. Contains the reason.
+    InvalidHeader(&'static str),
+    /// The primary index does not fit the block.
+    InvalidIndex,
+    /// The data ended in the middle of a run or block.
+    Truncated,
+    /// The Huffman backend failed.
+    Huffman(HuffmanError),
+}
+
+impl fmt::Display for BwtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BwtError::InvalidHeader(s) => write!(f, "Invalid header: {}", s),
+            BwtError::InvalidIndex     => write!(f, "The primary index does not fit the block."),
+            BwtError::Truncated        => write!(f, "The data is truncated."),
+            BwtError::Huffman(e)       => write!(f, "Huffman error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for BwtError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BwtError::Huffman(e) => Some(e),
+            _                    => None,
+        }
+    }
+}
+
+impl From<HuffmanError> for BwtError {
+    fn from(e: HuffmanError) -> BwtError {
+        BwtError::Huffman(e)
+    }
+}
+
+/// Computes the suffix array of `data` by prefix doubling with counting sorts in `O(n log n)`.
+/// # Arguments
+/// * `data` - The data to be sorted.
+/// # Returns
+/// A `Vec<usize>` with the starting positions of all suffixes in lexicographic order.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::bwt::suffix_array;
+///
+/// assert_eq!(suffix_array(b"banana"), vec![5, 3, 1, 0, 4, 2]);
+/// ```
+#[must_use]
+pub fn suffix_array(data: &[u8]) -> Vec<usize> {
+    // Sorting the rotations of `data` with a unique, smallest sentinel appended is the same as sorting its suffixes.
+    let n:           usize      = data.len() + 1;
+    let mut sa:      Vec<usize> = vec![0; n];
+    let mut class:   Vec<usize> = data.iter().map(|b| *b as usize + 1).chain(std::iter::once(0)).collect();
+    let mut count:   Vec<usize> = vec![0; n.max(257)];
+    let mut shifted: Vec<usize> = vec![0; n];
+    let mut next:    Vec<usize> = vec![0; n];
+
+    for c in &class
+    { count[*c] += 1; }
+    for i in 1..count.len()
+    { count[i] += count[i - 1]; }
+    for i in (0..n).rev() {
+        count[class[i]] -= 1;
+        sa[count[class[i]]] = i;
+    }
+    let mut classes: usize = rank(&sa, &mut class, |a, b| data.get(a) == data.get(b));
+
+    let mut k: usize = 1;
+    while k < n && classes < n {
+        // The second halves are already sorted, so sorting by the first half is enough.
+        for (s, x) in shifted.iter_mut().zip(&sa)
+        { *s = (x + n - k) % n; }
+        count[..classes].iter_mut().for_each(|x| *x = 0);
+        for s in &shifted
+        { count[class[*s]] += 1; }
+        for i in 1..classes
+        { count[i] += count[i - 1]; }
+        for s in shifted.iter().rev() {
+            count[class[*s]] -= 1;
+            sa[count[class[*s]]] = *s;
+        }
+
+        next.copy_from_slice(&class);
+        classes = rank(&sa, &mut next, |a, b| class[a] == class[b] && class[(a + k) % n] == class[(b + k) % n]);
+        std::mem::swap(&mut class, &mut next);
+        k *= 2;
+    }
+    sa.remove(0);
+    sa
+}
+
+/// Assigns equivalence classes in the order of `sa`. Returns the number of classes.
+fn rank<F: Fn(usize, usize) -> bool>(sa: &[usize], class: &mut [usize], equal: F) -> usize {
+    let mut classes: usize = 1;
+    class[sa[0]] = 0;
+    for w in sa.windows(2) {
+        if !equal(w[0], w[1])
+        { classes += 1; }
+        class[w[1]] = classes - 1;
+    }
+    classes
+}
+
+/// Applies the Burrows-Wheeler transform.
+/// # Arguments
+/// * `data` - The data to be transformed.
+/// # Returns
+/// A `(Vec<u8>, usize)` with the last column of the sorted rotations and the primary index needed to undo the transform.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::bwt::{bwt, inverse_bwt};
+/// let (res, primary) = bwt(b"banana");
+///
+/// assert_eq!(res, b"annbaa");
+/// assert_eq!(inverse_bwt(&res, primary).unwrap(), b"banana");
+/// ```
+#[must_use]
+pub fn bwt(data: &[u8]) -> (Vec<u8>, usize) {
+    let mut res:     Vec<u8> = Vec::with_capacity(data.len());
+    let mut primary: usize   = 0;
+
+    // The first row is the rotation starting with the sentinel, which is left out of the output.
+    if let Some(last) = data.last()
+    { res.push(*last); }
+    for (i, s) in suffix_array(data).into_iter().enumerate() {
+        match s {
+            0 => { primary = i + 1; }
+            _ => { res.push(data[s - 1]); }
+        }
+    }
+    (res, primary)
+}
+
+/// Undoes the Burrows-Wheeler transform.
+/// # Arguments
+/// * `data` - The transformed data.
+/// * `primary` - The primary index returned by `bwt`.
+/// # Returns
+/// A `Result<Vec<u8>, BwtError>`. Fails with `BwtError::InvalidIndex` if `primary` is out of range.
+pub fn inverse_bwt(data: &[u8], primary: usize) -> Result<Vec<u8>, BwtError> {
+    if data.is_empty()
+    { return if primary == 0 { Ok(Vec::new()) } else { Err(BwtError::InvalidIndex) }; }
+    if primary == 0 || primary > data.len()
+    { return Err(BwtError::InvalidIndex); }
+
+    // Row `primary` of the last column holds the sentinel, which sorts before every byte.
+    let mut start: [usize; 256] = [0; 256];
+    for b in data
+    { start[*b as usize] += 1; }
+    let mut sum: usize = 1;
+    for s in start.iter_mut() {
+        let c: usize = *s;
+        *s   = sum;
+        sum += c;
+    }
+
+    // `lf[row]` is the row of the rotation starting one byte earlier.
+    let mut lf: Vec<usize> = vec![0; data.len() + 1];
+    for (i, b) in data.iter().enumerate() {
+        let row: usize = if i < primary { i } else { i + 1 };
+        lf[row] = start[*b as usize];
+        start[*b as usize] += 1;
+    }
+
+    let mut res: Vec<u8> = vec![0; data.len()];
+    let mut row: usize   = 0;
+    for x in res.iter_mut().rev() {
+        *x  = data[if row < primary { row } else { row - 1 }];
+        row = lf[row];
+    }
+    Ok(res)
+}
+
+/// Applies move-to-front coding: every byte is replaced by its position in a list of recently used bytes.
+/// # Arguments
+/// * `data` - The data to be coded.
+/// # Returns
+/// A `Vec<u8>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::bwt::{mtf_encode, mtf_decode};
+/// let enc = mtf_encode(b"aaabbbaaa");
+///
+/// assert_eq!(enc, vec![97, 0, 0, 98, 0, 0, 1, 0, 0]);
+/// assert_eq!(mtf_decode(&enc), b"aaabbbaaa");
+/// ```
+#[must_use]
+pub fn mtf_encode(data: &[u8]) -> Vec<u8> {
+    let mut list: [u8; 256] = mtf_list();
+    data.iter()
+        .map(|b| {
+            let i: usize = list.iter().position(|x| x == b).unwrap();
+            list.copy_within(0..i, 1);
+            list[0] = *b;
+            i as u8
+        })
+        .collect()
+}
+
+/// Undoes move-to-front coding.
+/// # Arguments
+/// * `data` - The coded data.
+/// # Returns
+/// A `Vec<u8>`.
+#[must_use]
+pub fn mtf_decode(data: &[u8]) -> Vec<u8> {
+    let mut list: [u8; 256] = mtf_list();
+    data.iter()
+        .map(|i| {
+            let b: u8 = list[*i as usize];
+            list.copy_within(0..*i as usize, 1);
+            list[0] = b;
+            b
+        })
+        .collect()
+}
+
+fn mtf_list() -> [u8; 256] {
+    let mut list: [u8; 256] = [0; 256];
+    for (i, x) in list.iter_mut().enumerate()
+    { *x = i as u8; }
+    list
+}
+
+/// Run-length encodes bytes like the first stage of bzip2:
+/// after four equal bytes, a count of up to 255 further repetitions follows.
+/// # Arguments
+/// * `data` - The data to be encoded.
+/// # Returns
+/// A `Vec<u8>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::bwt::{rle_encode, rle_decode};
+/// let enc = rle_encode(b"abbbbbbbc");
+///
+/// assert_eq!(enc, b"abbbb\x03c");
+/// assert_eq!(rle_decode(&enc).unwrap(), b"abbbbbbbc");
+/// ```
+#[must_use]
+pub fn rle_encode(data: &[u8]) -> Vec<u8> {
+    let mut res: Vec<u8> = Vec::with_capacity(data.len());
+    let mut i:   usize   = 0;
+
+    while i < data.len() {
+        let run: usize = data[i..].iter()
+                                  .take(RUN_START + 255)
+                                  .take_while(|b| **b == data[i])
+                                  .count();
+        res.extend_from_slice(&data[i..i + run.min(RUN_START)]);
+        if run >= RUN_START
+        { res.push((run - RUN_START) as u8); }
+        i += run;
+    }
+    res
+}
+
+/// Undoes `rle_encode`.
+/// # Arguments
+/// * `data` - The encoded data.
+/// # Returns
+/// A `Result<Vec<u8>, BwtError>`. Fails with `BwtError::Truncated` if a run is missing its count.
+pub fn rle_decode(data: &[u8]) -> Result<Vec<u8>, BwtError> {
+    let mut res: Vec<u8> = Vec::with_capacity(data.len() * 2);
+    let mut run: usize   = 0;
+    let mut i:   usize   = 0;
+
+    while i < data.len() {
+        let b: u8 = data[i];
+        run = if res.last() == Some(&b) { run + 1 } else { 1 };
+        res.push(b);
+        i += 1;
+        if run == RUN_START {
+            let count: u8 = *data.get(i).ok_or(BwtError::Truncated)?;
+            res.extend(std::iter::repeat_n(b, count as usize));
+            run  = 0;
+            i   += 1;
+        }
+    }
+    Ok(res)
+}
+
+/// Compresses data with the whole block-sorting pipeline.
+/// # Arguments
+/// * `data` - The data to be compressed.
+/// * `block_size` - The number of bytes sorted at once. Bigger blocks compress better, but need more memory.
+/// # Returns
+/// A `Vec<u8>`.
+/// # Panics
+/// Panics if `block_size` is `0` or does not fit into a `u32`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::bwt::{compress, decompress, DEFAULT_BLOCK_SIZE};
+/// use lib_rapid::compsci::compression::huffman::Encode;
+/// // A made-up DNA sequence with a few mutated repeats.
+/// let data: Vec<u8> = (0..20_000u32).map(|i| b"ACGT"[((i % 97) * (i % 89 / 44 + 1) % 4) as usize]).collect();
+/// let enc = compress(&data, DEFAULT_BLOCK_SIZE);
+///
+/// assert!(enc.len() * 4 < data.full_encode().0.len() / 8);
+/// assert_eq!(decompress(&enc).unwrap(), data);
+/// ```
+#[must_use]
+pub fn compress(data: &[u8], block_size: usize) -> Vec<u8> {
+    if block_size == 0 || block_size > u32::MAX as usize
+    { panic!("The block size must be in the range of 1 to u32::MAX."); }
+
+    let mut res: Vec<u8> = Vec::with_capacity(data.len() / 2);
+    res.extend_from_slice(&BWT_MAGIC);
+    res.push(BWT_VERSION);
+
+    for block in data.chunks(block_size) {
+        let (transformed, primary) = bwt(block);
+        res.extend_from_slice(&(block.len() as u32).to_le_bytes());
+        res.extend_from_slice(&(primary as u32).to_le_bytes());
+
+        let mut writer: HuffmanWriter<Vec<u8>> = HuffmanWriter::new(res);
+        // Writing into a `Vec` can't fail.
+        writer.write_all(&rle_encode(&mtf_encode(&transformed))).unwrap();
+        res = writer.finish().unwrap();
+    }
+    res.extend_from_slice(&0u32.to_le_bytes());
+    res
+}
+
+/// Decompresses data compressed by `compress`.
+/// # Arguments
+/// * `data` - The compressed data.
+/// # Returns
+/// A `Result<Vec<u8>, BwtError>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::bwt::{compress, decompress, BwtError, DEFAULT_BLOCK_SIZE};
+/// use lib_rapid::compsci::compression::huffman::HuffmanError;
+/// let mut enc = compress(b"Lorem ipsum dolor sit amet.", DEFAULT_BLOCK_SIZE);
+/// let end: usize = enc.len() - 8;
+/// enc[end - 1] ^= 0x01; // Corrupt the checksum in front of both end markers.
+///
+/// assert!(matches!(decompress(&enc), Err(BwtError::Huffman(HuffmanError::ChecksumMismatch))));
+/// ```
+pub fn decompress(mut data: &[u8]) -> Result<Vec<u8>, BwtError> {
+    if data.len() < 5 || data[0..4] != BWT_MAGIC
+    { return Err(BwtError::InvalidHeader("Not a LibRapid block-sorting stream.")); }
+    if data[4] != BWT_VERSION
+    { return Err(BwtError::InvalidHeader("Unsupported version.")); }
+    data = &data[5..];
+
+    let mut res: Vec<u8> = Vec::new();
+    loop {
+        let len: usize = read_u32(&mut data)? as usize;
+        if len == 0
+        { return Ok(res); }
+        let primary: usize = read_u32(&mut data)? as usize;
+
+        let mut reader: HuffmanReader<&[u8]> = HuffmanReader::new(data);
+        let mut coded:  Vec<u8>              = Vec::new();
+        reader.read_to_end(&mut coded).map_err(|e| BwtError::Huffman(e.into()))?;
+        data = reader.into_inner();
+
+        let transformed: Vec<u8> = mtf_decode(&rle_decode(&coded)?);
+        if transformed.len() != len
+        { return Err(BwtError::InvalidHeader("Block length does not match its contents.")); }
+        res.extend(inverse_bwt(&transformed, primary)?);
+    }
+}
+
+fn read_u32(data: &mut &[u8]) -> Result<u32, BwtError> {
+    let mut bytes: [u8; 4] = [0; 4];
+    data.read_exact(&mut bytes).map_err(|_| BwtError::Truncated)?;
+    Ok(u32::from_le_bytes(bytes))
+}
\ No newline at end of file
@@ -0,0 +1,692 @@
+//! Implements DEFLATE (RFC 1951) together with the zlib (RFC 1950) and gzip (RFC 1952) containers.
+//! The output can be read by any other implementation, e.g. `zlib`, `gzip` or `7-Zip`, and vice versa.
+//! # Structure
+//! The encoder finds matches with the LZSS tokenizer and codes them with length-limited canonical Huffman codes.
+//! Every block is written in whichever form is the smallest: stored, with the fixed codes or with its own dynamic codes.
+//! The decoder supports all three block types and resolves codes with the table-driven Huffman decoder.
+use core::fmt;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use bit_vec::BitVec;
+
+use super::huffman::{HuffmanError, limited_code_lengths, assign_canonical_codes, table::{DecodeTable, BitSource}};
+use crate::compsci::checksum::{crc::crc32, adler::adler32};
+use super::lzss::{LzssConfig, Token, tokenize, copy_match};
+
+/// The largest distance a match may reach back.
+pub const WINDOW_SIZE: usize = 32768;
+/// The number of tokens coded with the same Huffman codes.
+const BLOCK_TOKENS: usize = 1 << 14;
+/// The largest number of bytes in a stored block.
+const MAX_STORED:   usize = 65535;
+const END_OF_BLOCK: u16   = 256;
+
+const LENGTH_BASE:  [u16; 29] = [3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31,
+                                 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258];
+const LENGTH_EXTRA: [u8; 29]  = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2,
+                                 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+const DIST_BASE:    [u16; 30] = [1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193,
+                                 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577];
+const DIST_EXTRA:   [u8; 30]  = [0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6,
+                                 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13];
+/// The order in which the lengths of the code length code are stored.
+const CL_ORDER:     [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+const FHCRC:      u8      = 0x02;
+const FEXTRA:     u8      = 0x04;
+const FNAME:      u8      = 0x08;
+const FCOMMENT:   u8      = 0x10;
+
+/// The errors that can occur while decompressing DEFLATE, zlib or gzip data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeflateError {
+    /// The data ended before the stream was complete.
+    Truncated,
+    /// A block uses the reserved block type `3`.
+    InvalidBlockType,
+    /// The length of a stored block does not match its one's complement.
+    StoredLengthMismatch,
+    /// The code lengths of a dynamic block don't describe a valid code. Contains the reason.
+    InvalidCodeLengths(&'static str),
+    /// The data contains a bit sequence or a symbol that is not part of the code.
+    InvalidCode,
+    /// A match points before the start of the output.
+    InvalidDistance,
+    /// The zlib or gzip header is malformed. Contains the reason.
+    InvalidHeader(&'static str),
+    /// The checksum in the trailer does not match the data.
+    ChecksumMismatch,
+    /// The length in the gzip trailer does not match the data.
+    LengthMismatch,
+}
+
+impl fmt::Display for DeflateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeflateError::Truncated             => write!(f, "The compressed data is truncated."),
+            DeflateError::InvalidBlockType      => write!(f, "Invalid block type."),
+            DeflateError::StoredLengthMismatch  => write!(f, "The length of a stored block does not match its complement."),
+            DeflateError::InvalidCodeLengths(s) => write!(f, "Invalid code lengths: {}", s),
+            DeflateError::InvalidCode           => write!(f, "The data contains an invalid code."),
+            DeflateError::InvalidDistance       => write!(f, "A match points before the start of the data."),
+            DeflateError::InvalidHeader(s)      => write!(f, "Invalid header: {}", s),
+            DeflateError::ChecksumMismatch      => write!(f, "Checksum mismatch."),
+            DeflateError::LengthMismatch        => write!(f, "The length of the data does not match the trailer."),
+        }
+    }
+}
+
+impl std::error::Error for DeflateError { }
+
+impl From<HuffmanError> for DeflateError {
+    fn from(e: HuffmanError) -> DeflateError {
+        match e {
+            HuffmanError::TruncatedBitstream => DeflateError::Truncated,
+            _                                => DeflateError::InvalidCode,
+        }
+    }
+}
+
+/// Compresses data into a raw DEFLATE stream.
+/// # Arguments
+/// * `data` - The data to be compressed.
+/// # Returns
+/// A `Vec<u8>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::deflate::{deflate, inflate};
+/// let data = "Lorem ipsum dolor sit amet, Lorem ipsum dolor sit amet.".repeat(10);
+/// let enc  = deflate(data.as_bytes());
+///
+/// assert!(enc.len() < data.len() / 5);
+/// assert_eq!(inflate(&enc).unwrap(), data.as_bytes());
+/// assert_eq!(deflate(b""), vec![0x03, 0x00]);
+/// ```
+#[must_use]
+pub fn deflate(data: &[u8]) -> Vec<u8> {
+    let tokens:     Vec<Token> = tokenize(data, &LzssConfig::new(WINDOW_SIZE, 3, 258));
+    let mut writer: BitWriter  = BitWriter::with_capacity(data.len() / 2 + 16);
+    let mut start:  usize      = 0;
+
+    if tokens.is_empty() {
+        write_block(&mut writer, &[], &[], true);
+    }
+    for (i, block) in tokens.chunks(BLOCK_TOKENS).enumerate() {
+        let len: usize = block.iter()
+                              .map(|t| match *t {
+                                  Token::Literal(_)          => 1,
+                                  Token::Match { length, .. } => length,
+                              })
+                              .sum();
+        let last: bool = (i + 1) * BLOCK_TOKENS >= tokens.len();
+        write_block(&mut writer, block, &data[start..start + len], last);
+        start += len;
+    }
+    writer.finish()
+}
+
+/// Decompresses a raw DEFLATE stream. Data after the end of the stream is ignored.
+/// # Arguments
+/// * `data` - The compressed data.
+/// # Returns
+/// A `Result<Vec<u8>, DeflateError>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::deflate::{inflate, DeflateError};
+/// // A block with the fixed codes, as written by zlib.
+/// let enc = [0xF3, 0x48, 0xCD, 0xC9, 0xC9, 0xD7, 0x51, 0xF0, 0x00, 0x51, 0x00];
+///
+/// assert_eq!(inflate(&enc).unwrap(), b"Hello, Hello");
+/// assert_eq!(inflate(&enc[..4]), Err(DeflateError::Truncated));
+/// ```
+pub fn inflate(data: &[u8]) -> Result<Vec<u8>, DeflateError> {
+    inflate_prefix(data).map(|(res, _)| res)
+}
+
+/// Compresses data into a zlib stream.
+/// # Arguments
+/// * `data` - The data to be compressed.
+/// # Returns
+/// A `Vec<u8>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::deflate::{zlib_compress, zlib_decompress};
+/// let enc = zlib_compress(b"Lorem ipsum dolor sit amet.");
+///
+/// assert_eq!(enc[0..2], [0x78, 0x9C]);
+/// assert_eq!(zlib_decompress(&enc).unwrap(), b"Lorem ipsum dolor sit amet.");
+/// ```
+#[must_use]
+pub fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    // Deflate with a 32 KiB window and the default compression level.
+    let mut res: Vec<u8> = vec![0x78, 0x9C];
+    res.extend(deflate(data));
+    res.extend_from_slice(&adler32(data).to_be_bytes());
+    res
+}
+
+/// Decompresses a zlib stream and verifies its Adler-32 checksum.
+/// # Arguments
+/// * `data` - The compressed data.
+/// # Returns
+/// A `Result<Vec<u8>, DeflateError>`. Streams that need a preset dictionary are rejected.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::deflate::{zlib_compress, zlib_decompress, DeflateError};
+/// let mut enc = zlib_compress(b"Lorem ipsum dolor sit amet.");
+/// let last    = enc.len() - 1;
+/// enc[last] ^= 1;
+///
+/// assert_eq!(zlib_decompress(&enc), Err(DeflateError::ChecksumMismatch));
+/// ```
+pub fn zlib_decompress(data: &[u8]) -> Result<Vec<u8>, DeflateError> {
+    if data.len() < 2
+    { return Err(DeflateError::Truncated); }
+    let (cmf, flg) = (data[0], data[1]);
+    if cmf & 0x0F != 8 || cmf >> 4 > 7
+    { return Err(DeflateError::InvalidHeader("Not a deflate stream.")); }
+    if !(cmf as u16 * 256 + flg as u16).is_multiple_of(31)
+    { return Err(DeflateError::InvalidHeader("Header check failed.")); }
+    if flg & 0x20 != 0
+    { return Err(DeflateError::InvalidHeader("Preset dictionaries are not supported.")); }
+
+    let (res, used) = inflate_prefix(&data[2..])?;
+    let trailer: &[u8] = data.get(2 + used..2 + used + 4).ok_or(DeflateError::Truncated)?;
+    if adler32(&res) != u32::from_be_bytes(trailer.try_into().unwrap())
+    { return Err(DeflateError::ChecksumMismatch); }
+    Ok(res)
+}
+
+/// Compresses data into a gzip file without a file name or time stamp.
+/// # Arguments
+/// * `data` - The data to be compressed.
+/// # Returns
+/// A `Vec<u8>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::deflate::{gzip_compress, gzip_decompress};
+/// let data = "Lorem ipsum dolor sit amet.".repeat(100);
+/// let enc  = gzip_compress(data.as_bytes());
+///
+/// assert_eq!(gzip_decompress(&enc).unwrap(), data.as_bytes());
+/// ```
+#[must_use]
+pub fn gzip_compress(data: &[u8]) -> Vec<u8> {
+    // No flags, no modification time, no extra flags, unknown operating system.
+    let mut res: Vec<u8> = vec![GZIP_MAGIC[0], GZIP_MAGIC[1], 8, 0, 0, 0, 0, 0, 0, 255];
+    res.extend(deflate(data));
+    res.extend_from_slice(&crc32(data).to_le_bytes());
+    res.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    res
+}
+
+/// Decompresses a gzip file and verifies the CRC-32 and length of every member.
+/// Concatenated members are decompressed one after another, like `gzip -d` does.
+/// # Arguments
+/// * `data` - The compressed data.
+/// # Returns
+/// A `Result<Vec<u8>, DeflateError>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::deflate::{gzip_compress, gzip_decompress};
+/// let mut enc = gzip_compress(b"Lorem ipsum ");
+/// enc.extend(gzip_compress(b"dolor sit amet."));
+///
+/// assert_eq!(gzip_decompress(&enc).unwrap(), b"Lorem ipsum dolor sit amet.");
+/// ```
+pub fn gzip_decompress(data: &[u8]) -> Result<Vec<u8>, DeflateError> {
+    let mut res: Vec<u8> = Vec::new();
+    let mut pos: usize   = 0;
+
+    loop {
+        let member: &[u8] = &data[pos..];
+        if member.len() < 10
+        { return Err(DeflateError::Truncated); }
+        if member[0..2] != GZIP_MAGIC
+        { return Err(DeflateError::InvalidHeader("Not a gzip file.")); }
+        if member[2] != 8
+        { return Err(DeflateError::InvalidHeader("Unsupported compression method.")); }
+
+        let flags:      u8    = member[3];
+        let mut header: usize = 10;
+        if flags & FEXTRA != 0 {
+            let len: &[u8] = member.get(header..header + 2).ok_or(DeflateError::Truncated)?;
+            header += 2 + u16::from_le_bytes([len[0], len[1]]) as usize;
+        }
+        for flag in [FNAME, FCOMMENT] {
+            if flags & flag != 0 {
+                let end: usize = member.get(header..)
+                                       .and_then(|x| x.iter().position(|b| *b == 0))
+                                       .ok_or(DeflateError::Truncated)?;
+                header += end + 1;
+            }
+        }
+        if flags & FHCRC != 0 {
+            let crc: &[u8] = member.get(header..header + 2).ok_or(DeflateError::Truncated)?;
+            if crc32(&member[..header]) as u16 != u16::from_le_bytes([crc[0], crc[1]])
+            { return Err(DeflateError::InvalidHeader("Header checksum mismatch.")); }
+            header += 2;
+        }
+
+        let (out, used) = inflate_prefix(member.get(header..).ok_or(DeflateError::Truncated)?)?;
+        let trailer: &[u8] = member.get(header + used..header + used + 8).ok_or(DeflateError::Truncated)?;
+        if crc32(&out) != u32::from_le_bytes(trailer[0..4].try_into().unwrap())
+        { return Err(DeflateError::ChecksumMismatch); }
+        if out.len() as u32 != u32::from_le_bytes(trailer[4..8].try_into().unwrap())
+        { return Err(DeflateError::LengthMismatch); }
+
+        res.extend(out);
+        pos += header + used + 8;
+        if pos == data.len()
+        { return Ok(res); }
+    }
+}
+
+/// Writes bits least significant first, as DEFLATE expects them.
+struct BitWriter {
+    out:    Vec<u8>,
+    buffer: u64,
+    count:  u8,
+}
+
+impl BitWriter {
+    fn with_capacity(capacity: usize) -> BitWriter {
+        BitWriter { out: Vec::with_capacity(capacity), buffer: 0, count: 0 }
+    }
+    #[inline]
+    fn write(&mut self, value: u32, n: u8) {
+        self.buffer |= (value as u64) << self.count;
+        self.count  += n;
+        while self.count >= 8 {
+            self.out.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count   -= 8;
+        }
+    }
+    /// Pads the output to a full byte.
+    fn align(&mut self) {
+        if self.count > 0
+        { self.write(0, 8 - self.count); }
+    }
+    fn finish(mut self) -> Vec<u8> {
+        self.align();
+        self.out
+    }
+}
+
+/// A Huffman code as the encoder needs it, indexed by symbol.
+struct Code {
+    lengths: Vec<u8>,
+    /// The codes with their bits reversed, so they can be written least significant first.
+    codes:   Vec<u32>,
+}
+
+impl Code {
+    /// Assigns the canonical codes for the given lengths. Symbols of length `0` are unused.
+    fn from_lengths(lengths: Vec<u8>) -> Code {
+        let mut used: Vec<(u16, u8)> = lengths.iter()
+                                              .enumerate()
+                                              .filter(|(_, l)| **l > 0)
+                                              .map(|(s, l)| (s as u16, *l))
+                                              .collect();
+        // The lengths come from `limited_code_lengths` or the fixed codes, which are always valid.
+        let canonical: HashMap<u16, BitVec> = assign_canonical_codes(&mut used).unwrap();
+        let mut codes: Vec<u32>             = vec![0; lengths.len()];
+        for (s, bits) in canonical {
+            let value: u32 = bits.iter().fold(0, |acc, b| (acc << 1) | b as u32);
+            codes[s as usize] = value.reverse_bits() >> (32 - bits.len());
+        }
+        Code { lengths, codes }
+    }
+    /// Builds a length-limited Huffman code for `frequency`, which must contain at least one used symbol.
+    fn from_frequencies(frequency: &[usize], max_len: u8) -> Code {
+        let used: HashMap<u16, usize> = frequency.iter()
+                                                 .enumerate()
+                                                 .filter(|(_, f)| **f > 0)
+                                                 .map(|(s, f)| (s as u16, *f))
+                                                 .collect();
+        let mut lengths: Vec<u8> = vec![0; frequency.len()];
+        // There are far less symbols than codes of `max_len` bits.
+        for (s, l) in limited_code_lengths(&used, max_len).unwrap() {
+            lengths[s as usize] = l;
+        }
+        Code::from_lengths(lengths)
+    }
+    #[inline]
+    fn write(&self, writer: &mut BitWriter, symbol: usize) {
+        writer.write(self.codes[symbol], self.lengths[symbol]);
+    }
+    /// The number of bits `frequency` costs with this code.
+    fn cost(&self, frequency: &[usize]) -> usize {
+        frequency.iter()
+                 .zip(&self.lengths)
+                 .map(|(f, l)| f * *l as usize)
+                 .sum()
+    }
+}
+
+fn fixed_lit_lengths() -> Vec<u8> {
+    (0..288).map(|s| match s {
+                0..=143   => 8,
+                144..=255 => 9,
+                256..=279 => 7,
+                _         => 8,
+            })
+            .collect()
+}
+
+#[inline]
+fn length_code(length: usize) -> usize {
+    LENGTH_BASE.partition_point(|b| *b as usize <= length) - 1
+}
+
+#[inline]
+fn distance_code(distance: usize) -> usize {
+    DIST_BASE.partition_point(|b| *b as usize <= distance) - 1
+}
+
+/// Writes the tokens of `raw` as a single block, or several stored blocks, whichever is the smallest.
+fn write_block(writer: &mut BitWriter, tokens: &[Token], raw: &[u8], last: bool) {
+    let mut lit_freq:  Vec<usize> = vec![0; 286];
+    let mut dist_freq: Vec<usize> = vec![0; 30];
+    let mut extra:     usize      = 0;
+    for t in tokens {
+        match *t {
+            Token::Literal(b)                 => { lit_freq[b as usize] += 1; }
+            Token::Match { distance, length } => {
+                let (l, d): (usize, usize) = (length_code(length), distance_code(distance));
+                lit_freq[257 + l] += 1;
+                dist_freq[d]      += 1;
+                extra             += (LENGTH_EXTRA[l] + DIST_EXTRA[d]) as usize;
+            }
+        }
+    }
+    lit_freq[END_OF_BLOCK as usize] = 1;
+
+    let fixed_lit:  Code  = Code::from_lengths(fixed_lit_lengths());
+    let fixed_dist: Code  = Code::from_lengths(vec![5; 30]);
+    let fixed_cost: usize = 3 + fixed_lit.cost(&lit_freq) + fixed_dist.cost(&dist_freq) + extra;
+
+    // A block without matches still has to describe at least one distance code.
+    if dist_freq.iter().all(|f| *f == 0)
+    { dist_freq[0] = 1; }
+    let lit:         Code             = Code::from_frequencies(&lit_freq, 15);
+    let dist:        Code             = Code::from_frequencies(&dist_freq, 15);
+    let hlit:        usize            = 257.max(lit.lengths.iter().rposition(|l| *l > 0).unwrap() + 1);
+    let hdist:       usize            = dist.lengths.iter().rposition(|l| *l > 0).unwrap() + 1;
+    let all_lengths: Vec<u8>          = [&lit.lengths[..hlit], &dist.lengths[..hdist]].concat();
+    let cl_symbols:  Vec<(usize, u8)> = run_length_encode(&all_lengths);
+    let mut cl_freq: Vec<usize>       = vec![0; 19];
+    for (s, _) in &cl_symbols
+    { cl_freq[*s] += 1; }
+    let cl:           Code  = Code::from_frequencies(&cl_freq, 7);
+    let hclen:        usize = 4.max(CL_ORDER.iter().rposition(|s| cl.lengths[*s] > 0).unwrap() + 1);
+    let cl_extra:     usize = cl_freq[16] * 2 + cl_freq[17] * 3 + cl_freq[18] * 7;
+    let dynamic_cost: usize = 3 + 14 + 3 * hclen + cl.cost(&cl_freq) + cl_extra
+                            + lit.cost(&lit_freq) + dist.cost(&dist_freq) + extra;
+
+    let chunks:      usize = raw.len().div_ceil(MAX_STORED).max(1);
+    let stored_cost: usize = chunks * (3 + 7 + 32) + raw.len() * 8;
+
+    if stored_cost < fixed_cost.min(dynamic_cost) {
+        let mut parts = raw.chunks(MAX_STORED).peekable();
+        if raw.is_empty()
+        { write_stored(writer, &[], last); }
+        while let Some(part) = parts.next() {
+            write_stored(writer, part, last && parts.peek().is_none());
+        }
+        return;
+    }
+
+    writer.write(last as u32, 1);
+    if fixed_cost <= dynamic_cost {
+        writer.write(1, 2);
+        write_tokens(writer, tokens, &fixed_lit, &fixed_dist);
+        return;
+    }
+
+    writer.write(2, 2);
+    writer.write((hlit - 257) as u32, 5);
+    writer.write((hdist - 1) as u32, 5);
+    writer.write((hclen - 4) as u32, 4);
+    for s in &CL_ORDER[..hclen] {
+        writer.write(cl.lengths[*s] as u32, 3);
+    }
+    for (s, bits) in &cl_symbols {
+        cl.write(writer, *s);
+        match s {
+            16 => { writer.write(*bits as u32, 2); }
+            17 => { writer.write(*bits as u32, 3); }
+            18 => { writer.write(*bits as u32, 7); }
+            _  => { }
+        }
+    }
+    write_tokens(writer, tokens, &lit, &dist);
+}
+
+fn write_stored(writer: &mut BitWriter, data: &[u8], last: bool) {
+    writer.write(last as u32, 1);
+    writer.write(0, 2);
+    writer.align();
+    writer.write(data.len() as u32, 16);
+    writer.write(!(data.len() as u32) & 0xFFFF, 16);
+    writer.out.extend_from_slice(data);
+}
+
+fn write_tokens(writer: &mut BitWriter, tokens: &[Token], lit: &Code, dist: &Code) {
+    for t in tokens {
+        match *t {
+            Token::Literal(b)                 => { lit.write(writer, b as usize); }
+            Token::Match { distance, length } => {
+                let (l, d): (usize, usize) = (length_code(length), distance_code(distance));
+                lit.write(writer, 257 + l);
+                writer.write((length - LENGTH_BASE[l] as usize) as u32, LENGTH_EXTRA[l]);
+                dist.write(writer, d);
+                writer.write((distance - DIST_BASE[d] as usize) as u32, DIST_EXTRA[d]);
+            }
+        }
+    }
+    lit.write(writer, END_OF_BLOCK as usize);
+}
+
+/// Codes a sequence of code lengths with the symbols `0..=15` and the repetitions `16`, `17` and `18`.
+/// Returns the symbols together with the values of their extra bits.
+fn run_length_encode(lengths: &[u8]) -> Vec<(usize, u8)> {
+    let mut res: Vec<(usize, u8)> = Vec::new();
+    let mut i:   usize            = 0;
+
+    while i < lengths.len() {
+        let len:     u8    = lengths[i];
+        let run:     usize = lengths[i..].iter().take_while(|l| **l == len).count();
+        let mut rem: usize = run;
+
+        if len == 0 {
+            while rem >= 11 {
+                let n: usize = rem.min(138);
+                res.push((18, (n - 11) as u8));
+                rem -= n;
+            }
+            if rem >= 3 {
+                res.push((17, (rem - 3) as u8));
+                rem = 0;
+            }
+        }
+        else {
+            res.push((len as usize, 0));
+            rem -= 1;
+            while rem >= 3 {
+                let n: usize = rem.min(6);
+                res.push((16, (n - 3) as u8));
+                rem -= n;
+            }
+        }
+        res.extend(std::iter::repeat_n((len as usize, 0), rem));
+        i += run;
+    }
+    res
+}
+
+/// Reads bits least significant first out of a byte slice.
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos:  usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> BitReader<'a> {
+        BitReader { data, pos: 0 }
+    }
+    /// At least the next 56 bits, least significant first. Missing bits at the end are `0`.
+    #[inline]
+    fn lookahead(&self) -> u64 {
+        let i: usize = self.pos / 8;
+        let word: u64 = match self.data.get(i..i + 8) {
+            Some(x) => u64::from_le_bytes(x.try_into().unwrap()),
+            None    => {
+                let mut bytes: [u8; 8] = [0; 8];
+                let rest:      &[u8]   = self.data.get(i..).unwrap_or(&[]);
+                bytes[..rest.len()].copy_from_slice(rest);
+                u64::from_le_bytes(bytes)
+            }
+        };
+        word >> (self.pos % 8)
+    }
+    /// Reads an `n`-bit integer (`0..=16`).
+    #[inline]
+    fn bits(&mut self, n: u8) -> Result<u32, DeflateError> {
+        if n as usize > self.remaining()
+        { return Err(DeflateError::Truncated); }
+        let value: u32 = (self.lookahead() & ((1 << n) - 1)) as u32;
+        self.pos += n as usize;
+        Ok(value)
+    }
+    fn align(&mut self) {
+        self.pos = self.pos.div_ceil(8) * 8;
+    }
+}
+
+impl BitSource for BitReader<'_> {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.data.len() * 8 - self.pos
+    }
+    /// Huffman codes are packed starting with their most significant bit.
+    #[inline]
+    fn peek(&mut self, n: u8) -> u64 {
+        self.lookahead().reverse_bits() >> (64 - n)
+    }
+    #[inline]
+    fn consume(&mut self, n: u8) {
+        self.pos += n as usize;
+    }
+}
+
+/// Decompresses a DEFLATE stream at the start of `data`.
+/// Returns the decompressed data and the number of bytes the stream took up.
+fn inflate_prefix(data: &[u8]) -> Result<(Vec<u8>, usize), DeflateError> {
+    let mut reader: BitReader = BitReader::new(data);
+    let mut res:    Vec<u8>   = Vec::with_capacity(data.len() * 3);
+
+    loop {
+        let last: bool = reader.bits(1)? == 1;
+        match reader.bits(2)? {
+            0 => { inflate_stored(&mut reader, &mut res)?; }
+            1 => {
+                let lit:  DecodeTable<u16> = table_from_lengths(&fixed_lit_lengths())?;
+                let dist: DecodeTable<u16> = table_from_lengths(&[5; 32])?;
+                inflate_block(&mut reader, &mut res, &lit, &dist)?;
+            }
+            2 => {
+                let (lit, dist) = read_dynamic_tables(&mut reader)?;
+                inflate_block(&mut reader, &mut res, &lit, &dist)?;
+            }
+            _ => { return Err(DeflateError::InvalidBlockType); }
+        }
+        if last
+        { return Ok((res, reader.pos.div_ceil(8))); }
+    }
+}
+
+fn inflate_stored(reader: &mut BitReader, res: &mut Vec<u8>) -> Result<(), DeflateError> {
+    reader.align();
+    let len:  u32 = reader.bits(16)?;
+    let nlen: u32 = reader.bits(16)?;
+    if len != !nlen & 0xFFFF
+    { return Err(DeflateError::StoredLengthMismatch); }
+
+    let start: usize = reader.pos / 8;
+    let bytes: &[u8] = reader.data.get(start..start + len as usize).ok_or(DeflateError::Truncated)?;
+    res.extend_from_slice(bytes);
+    reader.pos += len as usize * 8;
+    Ok(())
+}
+
+fn inflate_block(reader: &mut BitReader,
+                 res:    &mut Vec<u8>,
+                 lit:    &DecodeTable<u16>,
+                 dist:   &DecodeTable<u16>) -> Result<(), DeflateError> {
+    loop {
+        let symbol: usize = lit.decode_next(reader)? as usize;
+        match symbol {
+            0..=255   => { res.push(symbol as u8); }
+            256       => { return Ok(()); }
+            257..=285 => {
+                let l:      usize = symbol - 257;
+                let length: usize = LENGTH_BASE[l] as usize + reader.bits(LENGTH_EXTRA[l])? as usize;
+                let d:      usize = dist.decode_next(reader)? as usize;
+                if d >= 30
+                { return Err(DeflateError::InvalidCode); }
+                let distance: usize = DIST_BASE[d] as usize + reader.bits(DIST_EXTRA[d])? as usize;
+                if distance > res.len()
+                { return Err(DeflateError::InvalidDistance); }
+                copy_match(res, distance, length);
+            }
+            _         => { return Err(DeflateError::InvalidCode); }
+        }
+    }
+}
+
+fn read_dynamic_tables(reader: &mut BitReader) -> Result<(DecodeTable<u16>, DecodeTable<u16>), DeflateError> {
+    let hlit:  usize = reader.bits(5)? as usize + 257;
+    let hdist: usize = reader.bits(5)? as usize + 1;
+    let hclen: usize = reader.bits(4)? as usize + 4;
+    if hlit > 286 || hdist > 30
+    { return Err(DeflateError::InvalidCodeLengths("Too many symbols.")); }
+
+    let mut cl_lengths: [u8; 19] = [0; 19];
+    for s in &CL_ORDER[..hclen] {
+        cl_lengths[*s] = reader.bits(3)? as u8;
+    }
+    let cl: DecodeTable<u16> = table_from_lengths(&cl_lengths)?;
+
+    let mut lengths: Vec<u8> = Vec::with_capacity(hlit + hdist);
+    while lengths.len() < hlit + hdist {
+        let (value, count): (u8, u32) = match cl.decode_next(reader)? {
+            16 => {
+                let prev: u8 = *lengths.last().ok_or(DeflateError::InvalidCodeLengths("Nothing to repeat."))?;
+                (prev, 3 + reader.bits(2)?)
+            }
+            17 => (0, 3 + reader.bits(3)?),
+            18 => (0, 11 + reader.bits(7)?),
+            l  => (l as u8, 1),
+        };
+        if lengths.len() + count as usize > hlit + hdist
+        { return Err(DeflateError::InvalidCodeLengths("Repetition exceeds the number of symbols.")); }
+        lengths.extend(std::iter::repeat_n(value, count as usize));
+    }
+    if lengths[END_OF_BLOCK as usize] == 0
+    { return Err(DeflateError::InvalidCodeLengths("Missing end-of-block code.")); }
+
+    Ok((table_from_lengths(&lengths[..hlit])?, table_from_lengths(&lengths[hlit..])?))
+}
+
+/// Builds a decoding table out of the code length of every symbol, `0` meaning unused.
+fn table_from_lengths(lengths: &[u8]) -> Result<DecodeTable<u16>, DeflateError> {
+    let used: Vec<(u16, u8)> = lengths.iter()
+                                      .enumerate()
+                                      .filter(|(_, l)| **l > 0)
+                                      .map(|(s, l)| (s as u16, *l))
+                                      .collect();
+    DecodeTable::from_lengths(&used).map_err(|_| DeflateError::InvalidCodeLengths("The code is over-subscribed."))
+}
\ No newline at end of file
@@ -1,311 +1,1019 @@
-extern crate bit_vec;
-extern crate bincode;
-extern crate serde;
-
-use core::fmt;
-use std::fs::File;
-use std::io::{Read, Write};
-use std::{str, usize};
-use std::collections::HashMap;
-use bit_vec::BitVec;
-use serde::{Serialize, Deserialize};
-
-use crate::math::general::NumTools;
-/// Encoding trait for minimal code writing.
-pub trait Encode {
-    /// Fully encodes a String.
-    /// # Returns
-    /// A `(BitVec, Box<Node>)` tuple.
-    /// # Examples
-    /// ```
-    /// use lib_rapid::compsci::compression::huffman::{Node, Encode, Decode};
-    /// let s: &str = "Lorem Ipsum";
-    /// let enc = s.full_encode();
-    /// let dec = enc.full_decode();
-    /// 
-    /// assert_eq!("Lorem Ipsum".to_owned(), dec);
-    /// ```
-    #[must_use]
-    fn full_encode(&self) -> (BitVec, Box<Node>);
-}
-/// Decoding trait for minimal code writing.
-pub trait Decode {
-    /// Fully decodes a encoded String.
-    /// # Returns
-    /// A `String`.
-    /// # Examples
-    /// ```
-    /// use lib_rapid::compsci::compression::huffman::{Node, Encode, Decode};
-    /// let s: &str = "Lorem Ipsum";
-    /// let enc = s.full_encode();
-    /// let dec = enc.full_decode();
-    /// 
-    /// assert_eq!("Lorem Ipsum".to_owned(), dec);
-    /// ```
-    #[must_use]
-    fn full_decode(&self) -> String;
-}
-
-impl Encode for String {
-    fn full_encode(&self) -> (BitVec, Box<Node>) {
-        let root = get_root(self);
-        let mut char_codes: HashMap<char, BitVec> = HashMap::new();
-        assign_codes(&root, &mut char_codes, &mut BitVec::new());
-        (huffman_encode(self, &char_codes), root)
-    }
-}
-
-impl Encode for &str {
-    fn full_encode(&self) -> (BitVec, Box<Node>) {
-        let root = get_root(self);
-        let mut char_codes: HashMap<char, BitVec> = HashMap::new();
-        assign_codes(&root, &mut char_codes, &mut BitVec::new());
-        (huffman_encode(self, &char_codes), root)
-    }
-}
-
-impl Decode for (BitVec, Box<Node>) {
-    fn full_decode(&self) -> String {
-        decode_string(&self.0, &self.1)
-    }
-}
-
-type Link = Option<Box<Node>>;
-/// The struct for a node.
-#[derive(Serialize, Deserialize, PartialEq, Clone, Debug, Default)]
-pub struct Node {
-    pub character: Option<char>,
-    pub frequency: u128,
-    pub left:      Link,
-    pub right:     Link
-}
-
-impl Node {
-    /// Generate a new node.
-    fn new(freq: u128, c: Option<char>) -> Node {
-        Node {
-            frequency: freq,
-            character: c,
-            left:      None, 
-            right:     None,
-        }
-    }
-    
-    /// Alias for `Box::new(n)`.
-    fn new_box(n: Node) -> Box<Node> {
-        Box::new(n)
-    }
-}
-
-// This makes it possible to print Nodes
-impl fmt::Display for Node {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.character {
-            None => write!(f, "Count: {})", self.frequency),
-            _    => write!(f, "(Char: '{}', Count: {})", self.character.unwrap(), self.frequency),
-        }
-    }
-}
-/// Get the frequency of the characters in a given String.
-fn get_frequency(s: &str) -> HashMap<char, usize> {
-    let mut hm: HashMap<char, usize> = HashMap::new(); // Result
-    for c in s.chars() {
-        let counter: &mut usize = hm.entry(c)
-                                    .or_insert(0); // Inserts c if value is not present, returns mut ref
-        counter.inc(); // Increment by 1
-    }
-    hm
-}
-
-/// Assigns Huffman-codes to each character.
-/// # Arguments
-/// * `root` - The Huffman tree.
-/// * `hashmap` - The variable in which the result is stored.
-/// * `bitvec` - A temporary BitVec.
-/// # Returns
-/// Nothing.
-/// # Examples
-/// ```
-/// use lib_rapid::compsci::compression::huffman::{get_root, assign_codes};
-/// use bit_vec::BitVec;
-/// use std::collections::HashMap;
-/// let s = "Lorem Ipsum";
-/// let root = get_root(s);
-/// let mut char_codes:HashMap<char, BitVec> = HashMap::new();
-/// assign_codes(&root, &mut char_codes, &mut BitVec::new()); // Assigns codes to characters of s and stores them in char_codes.
-/// ```
-pub fn assign_codes(root: &Box<Node>, 
-                    hashmap: &mut HashMap<char, BitVec>, 
-                    bitvec:  &mut BitVec ) {
-    match root.character {
-        Some(character) => { hashmap.insert(character, bitvec.clone()); }
-
-        None => {
-            if let Some(ref l) = root.left
-            { bitvec.push(false);
-              assign_codes(l, hashmap, bitvec); }
-
-            if let Some(ref r) = root.right
-            { bitvec.push(true);
-              assign_codes(r, hashmap, bitvec); }
-        }
-    }
-    bitvec.pop();
-    // Thanks to Pencilcaseman for fixing an issue
-}
-
-/// Encodes a string.
-/// # Arguments
-/// * `s` - The string to be encoded.
-/// * `char_codes` - The assigned Huffman codes of the characters.
-/// # Returns
-/// A BitVec which contains the Huffman encoded string.
-/// # Examples
-/// ```
-/// use lib_rapid::compsci::compression::huffman::{get_root, assign_codes, huffman_encode};
-/// use bit_vec::BitVec;
-/// use std::collections::HashMap;
-/// let s = "Lorem Ipsum";
-/// let root = get_root(s);
-/// let mut char_codes:HashMap<char, BitVec> = HashMap::new();
-/// assign_codes(&root, &mut char_codes, &mut BitVec::new()); // Assigns codes to characters of s and stores them in char_codes.
-/// let enc = huffman_encode(s, &char_codes); // Encodes the String s into enc.
-/// ```
-#[must_use]
-pub fn huffman_encode(s: &str, char_codes: &HashMap<char, BitVec>) -> BitVec {
-    let mut res: BitVec = BitVec::with_capacity(s.len());
-    let mut t:   Option<&BitVec>;
-
-    for c in s.chars() {
-        t = char_codes.get(&c);
-        res.append(&mut t.cloned()
-           .unwrap());
-    }
-    res
-}
-/// Decodes a String.
-fn decode_string(bitvec: &BitVec, root: &Box<Node>) -> String {
-    let mut res:     String     = String::new();
-    let mut nodeptr: &Box<Node> = root;
-
-    for b in bitvec {
-        match b {
-            false => { if let Some(ref l) = nodeptr.left { nodeptr = l; } }
-            true  => { if let Some(ref r) = nodeptr.right { nodeptr = r; } }
-        }
-
-        if let Some(c) = nodeptr.character { res.push(c); nodeptr = root; }
-    }
-
-    res
-}
-
-/// Decodes a Huffman encoded BitVec.
-/// # Arguments
-/// * `bitvec` - The assigned Huffman codes of the characters.
-/// * `root` - The Huffman tree.
-/// # Returns
-/// A BitVec which contains the Huffman encoded string.
-/// # Examples
-/// ```
-/// use lib_rapid::compsci::compression::huffman::{get_root, assign_codes, huffman_encode, huffman_decode};
-/// use bit_vec::BitVec;
-/// use std::collections::HashMap;
-/// let s: &str = "Lorem Ipsum";
-/// let root = get_root(s);
-/// let mut bitvec = BitVec::new();
-/// let mut char_codes: HashMap<char, BitVec> = HashMap::new();
-/// assign_codes(&root, &mut char_codes, &mut bitvec); // Assigns codes to characters of s and stores them in char_codes.
-/// let enc = huffman_encode(s, &char_codes); // Encodes the String s into enc.
-/// let dec = huffman_decode(&enc, &root); // Decodes the BitVec which was created by the last line.
-/// 
-/// assert_eq!("Lorem Ipsum".to_owned(), dec);
-/// ```
-#[must_use]
-pub fn huffman_decode(bitvec: &BitVec, root: &Box<Node>) -> String {
-    decode_string(bitvec, root)
-}
-
-/// Gets the Huffman tree for a string.
-/// # Arguments
-/// * `s` - The string of which the tree should be created.
-/// # Returns
-/// A Box<Node> containing the entire tree.
-#[must_use]
-pub fn get_root(s: &str) -> Box<Node> {
-    let frequency = get_frequency(s);
-    let mut vec_nodes: Vec<Box<Node>> = frequency.iter().map(|x| Node::new_box(Node::new(*(x.1) as u128, Some(*(x.0))))).collect();
-    let mut a;
-    let mut b;
-    let mut c;
-
-    while vec_nodes.len() > 1 {
-        vec_nodes.sort_by(|a: &Box<Node>, b: &Box<Node>| (&(b.frequency)).cmp(&(a.frequency)));
-        a = vec_nodes.pop().unwrap();
-        b = vec_nodes.pop().unwrap();
-        c = Node::new_box(Node::new( a.frequency + b.frequency, None));
-
-        c.left  = Some(a);
-        c.right = Some(b);
-        vec_nodes.push(c);
-    }
-    vec_nodes.pop().unwrap()
-}
-
-/// Writes the encoded message into 2 files.
-/// # Arguments
-/// * `path` - The path to be written to.
-/// * `bitvec` - The Huffman codes assigned to the characters.
-/// * `root` - The Huffman tree.
-/// # Returns
-/// Nothing. Writes to 2 files:
-/// name.hlr: The main file in which the encoded message is stored.
-/// name.htlr: The file in which the huffman tree is stored.
-/// # Examples
-/// ```
-/// use lib_rapid::compsci::compression::huffman::{Encode, write_to_file};
-/// let s: &str = "Lorem Ipsum";
-/// 
-/// let enc = s.full_encode(); // Encodes the String s into enc.
-/// write_to_file("test".to_string(), &enc.0, &enc.1);
-/// ```
-pub fn write_to_file(path: String, bitvec: &BitVec, root: &Box<Node>) {
-    let mut mainfile: File = File::create(path.clone() + ".hlr").unwrap();
-    let mut treefile: File = File::create(path + ".htlr").unwrap();
-    let _                  = mainfile.write(&bitvec.to_bytes());
-    let _                  = treefile.write(&bincode::serialize(root).unwrap());
-}
-
-/// Reads and decodes the message stored in the .hlr and .htlr files.
-/// # Arguments
-/// * `path` - The path to be read from.
-/// # Returns
-/// A String containing the decoded message.
-/// # Examples
-/// ```
-/// use lib_rapid::compsci::compression::huffman::{Encode, Decode, write_to_file, read_from_file};
-/// let s: &str = "Lorem Ipsum123123123";
-/// 
-/// let enc = s.full_encode(); // Encodes the String s into enc.
-/// let dec = enc.full_decode(); // Decodes the BitVec which was created by the last line.
-/// write_to_file("test".to_string(), &enc.0, &enc.1);
-/// 
-/// let dec_written = read_from_file("test".to_string());
-/// assert_eq!(dec_written, "Lorem Ipsum123123123");
-/// ```
-#[must_use]
-pub fn read_from_file(path: String) -> String {
-    let mut encoded_file: File    = File::open(path.clone() + ".hlr").unwrap();
-    let mut tree_file:    File    = File::open(path + ".htlr").unwrap();
-    let mut enc_file:     Vec<u8> = Vec::<u8>::new();
-    let mut enc_tree:     Vec<u8> = Vec::<u8>::new();
-
-    let _ = encoded_file.read_to_end(&mut enc_file);
-    let _ = tree_file.read_to_end(&mut enc_tree);
-
-    let mut bitvec: BitVec = BitVec::from_bytes(&enc_file); // Get final bitvec
-    bitvec.pop();
-    let root:Box<Node> = bincode::deserialize(&enc_tree).unwrap();
-
-    huffman_decode(&bitvec, &root)
+extern crate bit_vec;
+extern crate serde;
+
+use core::fmt;
+use std::convert::TryInto;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::{str, usize};
+use std::collections::{HashMap, BinaryHeap};
+use std::cmp::Reverse;
+use std::hash::Hash;
+use bit_vec::BitVec;
+use serde::{Serialize, Deserialize};
+
+use crate::math::general::NumTools;
+use crate::compsci::checksum::crc::crc32;
+
+pub mod table;
+pub mod stream;
+pub mod adaptive;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+/// Encoding trait for minimal code writing.
+pub trait Encode {
+    /// The type of the symbols the input consists of.
+    type Symbol;
+    /// Fully encodes a String or byte slice.
+    /// # Returns
+    /// A `(BitVec, Box<Node<Self::Symbol>>)` tuple.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::compression::huffman::{Node, Encode, Decode};
+    /// let s: &str = "Lorem Ipsum";
+    /// let enc = s.full_encode();
+    /// let dec = enc.full_decode();
+    /// 
+    /// assert_eq!("Lorem Ipsum".to_owned(), dec);
+    /// ```
+    /// ```
+    /// use lib_rapid::compsci::compression::huffman::{Encode, Decode};
+    /// let bytes: &[u8] = &[0, 159, 146, 150, 0, 0];
+    /// let enc = bytes.full_encode();
+    /// 
+    /// assert_eq!(bytes.to_vec(), enc.full_decode());
+    /// ```
+    #[must_use]
+    fn full_encode(&self) -> (BitVec, Box<Node<Self::Symbol>>);
+}
+/// Decoding trait for minimal code writing.
+pub trait Decode {
+    /// The type of the decoded message.
+    type Output;
+    /// Fully decodes a encoded String or byte slice.
+    /// # Returns
+    /// A `String` for `char` trees, a `Vec<u8>` for byte trees.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::compression::huffman::{Node, Encode, Decode};
+    /// let s: &str = "Lorem Ipsum";
+    /// let enc = s.full_encode();
+    /// let dec = enc.full_decode();
+    /// 
+    /// assert_eq!("Lorem Ipsum".to_owned(), dec);
+    /// ```
+    #[must_use]
+    fn full_decode(&self) -> Self::Output;
+}
+
+impl Encode for String {
+    type Symbol = char;
+
+    fn full_encode(&self) -> (BitVec, Box<Node>) {
+        let root = get_root(self);
+        let mut char_codes: HashMap<char, BitVec> = HashMap::new();
+        assign_codes(&root, &mut char_codes, &mut BitVec::new());
+        (huffman_encode(self, &char_codes), root)
+    }
+}
+
+impl Encode for &str {
+    type Symbol = char;
+
+    fn full_encode(&self) -> (BitVec, Box<Node>) {
+        let root = get_root(self);
+        let mut char_codes: HashMap<char, BitVec> = HashMap::new();
+        assign_codes(&root, &mut char_codes, &mut BitVec::new());
+        (huffman_encode(self, &char_codes), root)
+    }
+}
+
+impl Encode for &[u8] {
+    type Symbol = u8;
+
+    fn full_encode(&self) -> (BitVec, Box<Node<u8>>) {
+        let root = get_root_of(self);
+        let mut codes: HashMap<u8, BitVec> = HashMap::new();
+        assign_codes(&root, &mut codes, &mut BitVec::new());
+        (encode_symbols(self, &codes).unwrap(), root)
+    }
+}
+
+impl Encode for Vec<u8> {
+    type Symbol = u8;
+
+    fn full_encode(&self) -> (BitVec, Box<Node<u8>>) {
+        self.as_slice().full_encode()
+    }
+}
+
+impl Decode for (BitVec, Box<Node>) {
+    type Output = String;
+
+    fn full_decode(&self) -> String {
+        decode_string(&self.0, &self.1)
+    }
+}
+
+impl Decode for (BitVec, Box<Node<u8>>) {
+    type Output = Vec<u8>;
+
+    fn full_decode(&self) -> Vec<u8> {
+        let mut res: Vec<u8> = Vec::new();
+        walk_tree(&self.0, &self.1, |b| res.push(b));
+        res
+    }
+}
+
+/// The errors that can occur while Huffman coding or reading and writing containers.
+#[derive(Debug)]
+pub enum HuffmanError {
+    /// The input was empty, so no tree could be built.
+    EmptyInput,
+    /// The symbol at the given index has no code assigned.
+    UnknownSymbol(usize),
+    /// The bitstream ended in the middle of a code.
+    TruncatedBitstream,
+    /// The tree or code table is malformed.
+    CorruptTree,
+    /// The tree is deeper than the 64 bits a code may have.
+    CodeTooLong,
+    /// The container is malformed. Contains the reason.
+    InvalidContainer(&'static str),
+    /// The checksum of a container did not match its contents.
+    ChecksumMismatch,
+    /// An underlying I/O error.
+    Io(io::Error),
+}
+
+impl fmt::Display for HuffmanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HuffmanError::EmptyInput          => write!(f, "Cannot build a Huffman tree from empty input."),
+            HuffmanError::UnknownSymbol(i)    => write!(f, "The symbol at index {} has no Huffman code.", i),
+            HuffmanError::TruncatedBitstream  => write!(f, "The bitstream ends in the middle of a code."),
+            HuffmanError::CorruptTree         => write!(f, "The Huffman tree or code table is corrupt."),
+            HuffmanError::CodeTooLong         => write!(f, "A Huffman code is longer than 64 bits."),
+            HuffmanError::InvalidContainer(s) => write!(f, "Invalid container: {}", s),
+            HuffmanError::ChecksumMismatch    => write!(f, "The container checksum does not match."),
+            HuffmanError::Io(e)               => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for HuffmanError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HuffmanError::Io(e) => Some(e),
+            _                   => None,
+        }
+    }
+}
+
+impl From<io::Error> for HuffmanError {
+    /// Unpacks errors which were packed by `From<HuffmanError> for io::Error`, so that they can still be matched on.
+    fn from(e: io::Error) -> HuffmanError {
+        match e.get_ref().map(|x| x.is::<HuffmanError>()) {
+            Some(true) => *e.into_inner().unwrap().downcast::<HuffmanError>().unwrap(),
+            _          => HuffmanError::Io(e),
+        }
+    }
+}
+
+impl From<HuffmanError> for io::Error {
+    fn from(e: HuffmanError) -> io::Error {
+        match e {
+            HuffmanError::Io(e) => e,
+            _                   => io::Error::new(io::ErrorKind::InvalidData, e),
+        }
+    }
+}
+
+type Link<T> = Option<Box<Node<T>>>;
+/// The struct for a node. `T` is the type of the symbols, `char` by default.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug, Default)]
+pub struct Node<T = char> {
+    pub character: Option<T>,
+    pub frequency: u128,
+    pub left:      Link<T>,
+    pub right:     Link<T>
+}
+
+impl<T> Node<T> {
+    /// Generate a new node.
+    fn new(freq: u128, c: Option<T>) -> Node<T> {
+        Node {
+            frequency: freq,
+            character: c,
+            left:      None, 
+            right:     None,
+        }
+    }
+    
+    /// Alias for `Box::new(n)`.
+    fn new_box(n: Node<T>) -> Box<Node<T>> {
+        Box::new(n)
+    }
+}
+
+// This makes it possible to print Nodes
+impl<T: fmt::Display> fmt::Display for Node<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.character {
+            None => write!(f, "Count: {})", self.frequency),
+            _    => write!(f, "(Char: '{}', Count: {})", self.character.as_ref().unwrap(), self.frequency),
+        }
+    }
+}
+/// Get the frequency of the characters in a given String.
+pub(super) fn get_frequency(s: &str) -> HashMap<char, usize> {
+    let mut hm: HashMap<char, usize> = HashMap::new(); // Result
+    for c in s.chars() {
+        let counter: &mut usize = hm.entry(c)
+                                    .or_insert(0); // Inserts c if value is not present, returns mut ref
+        counter.inc(); // Increment by 1
+    }
+    hm
+}
+
+/// Get the frequency of the symbols in a given slice.
+pub(super) fn get_frequency_of<T: Hash + Eq + Copy>(symbols: &[T]) -> HashMap<T, usize> {
+    let mut hm: HashMap<T, usize> = HashMap::new();
+    for s in symbols {
+        hm.entry(*s)
+          .or_insert(0)
+          .inc();
+    }
+    hm
+}
+
+/// Assigns Huffman-codes to each character or symbol.
+/// # Arguments
+/// * `root` - The Huffman tree.
+/// * `hashmap` - The variable in which the result is stored.
+/// * `bitvec` - A temporary BitVec.
+/// # Returns
+/// Nothing.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{get_root, assign_codes};
+/// use bit_vec::BitVec;
+/// use std::collections::HashMap;
+/// let s = "Lorem Ipsum";
+/// let root = get_root(s);
+/// let mut char_codes:HashMap<char, BitVec> = HashMap::new();
+/// assign_codes(&root, &mut char_codes, &mut BitVec::new()); // Assigns codes to characters of s and stores them in char_codes.
+/// ```
+pub fn assign_codes<T: Hash + Eq + Copy>(root:    &Node<T>, 
+                                          hashmap: &mut HashMap<T, BitVec>, 
+                                          bitvec:  &mut BitVec ) {
+    match root.character {
+        Some(character) => { hashmap.insert(character, bitvec.clone()); }
+
+        None => {
+            if let Some(ref l) = root.left
+            { bitvec.push(false);
+              assign_codes(l, hashmap, bitvec); }
+
+            if let Some(ref r) = root.right
+            { bitvec.push(true);
+              assign_codes(r, hashmap, bitvec); }
+        }
+    }
+    bitvec.pop();
+    // Thanks to Pencilcaseman for fixing an issue
+}
+
+/// Encodes a string.
+/// # Arguments
+/// * `s` - The string to be encoded.
+/// * `char_codes` - The assigned Huffman codes of the characters.
+/// # Returns
+/// A BitVec which contains the Huffman encoded string.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{get_root, assign_codes, huffman_encode};
+/// use bit_vec::BitVec;
+/// use std::collections::HashMap;
+/// let s = "Lorem Ipsum";
+/// let root = get_root(s);
+/// let mut char_codes:HashMap<char, BitVec> = HashMap::new();
+/// assign_codes(&root, &mut char_codes, &mut BitVec::new()); // Assigns codes to characters of s and stores them in char_codes.
+/// let enc = huffman_encode(s, &char_codes); // Encodes the String s into enc.
+/// ```
+/// # Panics
+/// Panics if a character of `s` has no code in `char_codes`. Use `try_huffman_encode` to handle this case.
+#[must_use]
+pub fn huffman_encode(s: &str, char_codes: &HashMap<char, BitVec>) -> BitVec {
+    try_huffman_encode(s, char_codes).unwrap()
+}
+
+/// Encodes a string, failing on characters without a code.
+/// # Arguments
+/// * `s` - The string to be encoded.
+/// * `char_codes` - The assigned Huffman codes of the characters.
+/// # Returns
+/// A `Result<BitVec, HuffmanError>`. `HuffmanError::UnknownSymbol` contains the character index of the first character without a code.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{get_root, assign_codes, try_huffman_encode, HuffmanError};
+/// use bit_vec::BitVec;
+/// use std::collections::HashMap;
+/// let root = get_root("Lorem");
+/// let mut char_codes: HashMap<char, BitVec> = HashMap::new();
+/// assign_codes(&root, &mut char_codes, &mut BitVec::new());
+/// 
+/// assert!(try_huffman_encode("Lore", &char_codes).is_ok());
+/// assert!(matches!(try_huffman_encode("Lorem Ipsum", &char_codes), Err(HuffmanError::UnknownSymbol(5))));
+/// ```
+pub fn try_huffman_encode(s: &str, char_codes: &HashMap<char, BitVec>) -> Result<BitVec, HuffmanError> {
+    encode_iter(s.chars(), s.len(), char_codes)
+}
+
+/// Encodes a slice of arbitrary symbols, such as bytes.
+/// # Arguments
+/// * `symbols` - The symbols to be encoded.
+/// * `codes` - The assigned Huffman codes of the symbols.
+/// # Returns
+/// A `Result<BitVec, HuffmanError>`. `HuffmanError::UnknownSymbol` contains the index of the first symbol without a code.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{get_root_of, assign_codes, encode_symbols, decode_symbols};
+/// use bit_vec::BitVec;
+/// use std::collections::HashMap;
+/// let data: Vec<u16> = vec![1000, 1000, 7, 65535, 1000];
+/// let root = get_root_of(&data);
+/// let mut codes: HashMap<u16, BitVec> = HashMap::new();
+/// assign_codes(&root, &mut codes, &mut BitVec::new());
+/// 
+/// let enc = encode_symbols(&data, &codes).unwrap();
+/// assert_eq!(decode_symbols(&enc, &root).unwrap(), data);
+/// ```
+pub fn encode_symbols<T: Hash + Eq + Copy>(symbols: &[T], codes: &HashMap<T, BitVec>) -> Result<BitVec, HuffmanError> {
+    encode_iter(symbols.iter().copied(), symbols.len(), codes)
+}
+
+fn encode_iter<T: Hash + Eq, I: Iterator<Item = T>>(symbols:  I,
+                                                   capacity: usize,
+                                                   codes:    &HashMap<T, BitVec>) -> Result<BitVec, HuffmanError> {
+    let mut res: BitVec = BitVec::with_capacity(capacity);
+
+    for (i, s) in symbols.enumerate() {
+        match codes.get(&s) {
+            Some(code) => { res.extend(code.iter()); }
+            None       => { return Err(HuffmanError::UnknownSymbol(i)); }
+        }
+    }
+    Ok(res)
+}
+/// Decodes a String.
+fn decode_string(bitvec: &BitVec, root: &Box<Node>) -> String {
+    let mut res: String = String::new();
+    walk_tree(bitvec, root, |c| res.push(c));
+    res
+}
+
+/// Walks the tree along `bitvec`, calling `f` for every decoded symbol. Bits without a branch are skipped.
+fn walk_tree<T: Copy, F: FnMut(T)>(bitvec: &BitVec, root: &Node<T>, mut f: F) {
+    let mut nodeptr: &Node<T> = root;
+
+    for b in bitvec {
+        match b {
+            false => { if let Some(ref l) = nodeptr.left { nodeptr = l; } }
+            true  => { if let Some(ref r) = nodeptr.right { nodeptr = r; } }
+        }
+
+        if let Some(c) = nodeptr.character { f(c); nodeptr = root; }
+    }
+}
+
+/// Walks the tree along `bitvec` like `walk_tree`, but fails on missing branches and incomplete codes.
+fn try_walk_tree<T: Copy, F: FnMut(T)>(bitvec: &BitVec, root: &Node<T>, mut f: F) -> Result<(), HuffmanError> {
+    let mut nodeptr: &Node<T> = root;
+
+    for b in bitvec {
+        let next: &Link<T> = match b {
+            false => &nodeptr.left,
+            true  => &nodeptr.right,
+        };
+        nodeptr = next.as_ref().ok_or(HuffmanError::CorruptTree)?;
+
+        if let Some(c) = nodeptr.character { f(c); nodeptr = root; }
+    }
+    if !std::ptr::eq(nodeptr, root)
+    { return Err(HuffmanError::TruncatedBitstream); }
+
+    Ok(())
+}
+
+/// Decodes a Huffman encoded BitVec.
+/// # Arguments
+/// * `bitvec` - The assigned Huffman codes of the characters.
+/// * `root` - The Huffman tree.
+/// # Returns
+/// A BitVec which contains the Huffman encoded string.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{get_root, assign_codes, huffman_encode, huffman_decode};
+/// use bit_vec::BitVec;
+/// use std::collections::HashMap;
+/// let s: &str = "Lorem Ipsum";
+/// let root = get_root(s);
+/// let mut bitvec = BitVec::new();
+/// let mut char_codes: HashMap<char, BitVec> = HashMap::new();
+/// assign_codes(&root, &mut char_codes, &mut bitvec); // Assigns codes to characters of s and stores them in char_codes.
+/// let enc = huffman_encode(s, &char_codes); // Encodes the String s into enc.
+/// let dec = huffman_decode(&enc, &root); // Decodes the BitVec which was created by the last line.
+/// 
+/// assert_eq!("Lorem Ipsum".to_owned(), dec);
+/// ```
+#[must_use]
+pub fn huffman_decode(bitvec: &BitVec, root: &Box<Node>) -> String {
+    decode_string(bitvec, root)
+}
+
+/// Decodes a Huffman encoded BitVec, validating it against the tree.
+/// Unlike `huffman_decode`, this does not skip over bits the tree has no branch for.
+/// # Arguments
+/// * `bitvec` - The Huffman encoded message.
+/// * `root` - The Huffman tree.
+/// # Returns
+/// A `Result<String, HuffmanError>`. Fails with `HuffmanError::CorruptTree` if the tree has no branch for a bit
+/// and with `HuffmanError::TruncatedBitstream` if the message ends in the middle of a code.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{Encode, try_huffman_decode, HuffmanError};
+/// let (mut enc, root) = "Lorem Ipsum".full_encode();
+/// assert_eq!(try_huffman_decode(&enc, &root).unwrap(), "Lorem Ipsum");
+/// 
+/// enc.pop();
+/// assert!(matches!(try_huffman_decode(&enc, &root), Err(HuffmanError::TruncatedBitstream)));
+/// ```
+pub fn try_huffman_decode(bitvec: &BitVec, root: &Node) -> Result<String, HuffmanError> {
+    let mut res: String = String::new();
+    try_walk_tree(bitvec, root, |c| res.push(c))?;
+    Ok(res)
+}
+
+/// Decodes a Huffman encoded BitVec of arbitrary symbols, validating it against the tree.
+/// # Arguments
+/// * `bitvec` - The Huffman encoded message.
+/// * `root` - The Huffman tree.
+/// # Returns
+/// A `Result<Vec<T>, HuffmanError>`, failing in the same cases as `try_huffman_decode`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{Encode, decode_symbols};
+/// let data: Vec<u8> = vec![0xDE, 0xAD, 0xBE, 0xEF, 0xEF];
+/// let (enc, root) = data.full_encode();
+/// 
+/// assert_eq!(decode_symbols(&enc, &root).unwrap(), data);
+/// ```
+pub fn decode_symbols<T: Copy>(bitvec: &BitVec, root: &Node<T>) -> Result<Vec<T>, HuffmanError> {
+    let mut res: Vec<T> = Vec::new();
+    try_walk_tree(bitvec, root, |s| res.push(s))?;
+    Ok(res)
+}
+
+/// Gets the Huffman tree for a string.
+/// # Arguments
+/// * `s` - The string of which the tree should be created.
+/// # Returns
+/// A Box<Node> containing the entire tree.
+/// # Panics
+/// Panics if `s` is empty. Use `try_get_root` to handle this case.
+#[must_use]
+pub fn get_root(s: &str) -> Box<Node> {
+    try_get_root(s).unwrap()
+}
+
+/// Gets the Huffman tree for a string, failing on empty input.
+/// A string with only one distinct character gets a tree with a single leaf on the left, so its code is `0`.
+/// # Arguments
+/// * `s` - The string of which the tree should be created.
+/// # Returns
+/// A `Result<Box<Node>, HuffmanError>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{try_get_root, Encode, Decode, HuffmanError};
+/// assert!(matches!(try_get_root(""), Err(HuffmanError::EmptyInput)));
+/// assert!(try_get_root("aaaa").unwrap().left.is_some());
+/// 
+/// let enc = "aaaa".full_encode();
+/// assert_eq!(enc.0.len(), 4);
+/// assert_eq!(enc.full_decode(), "aaaa");
+/// ```
+pub fn try_get_root(s: &str) -> Result<Box<Node>, HuffmanError> {
+    try_get_root_from_frequencies(&get_frequency(s))
+}
+
+/// Gets the Huffman tree for a slice of arbitrary symbols, such as bytes.
+/// # Arguments
+/// * `symbols` - The symbols of which the tree should be created.
+/// # Returns
+/// A `Box<Node<T>>` containing the entire tree.
+/// # Panics
+/// Panics if `symbols` is empty. Use `try_get_root_of` to handle this case.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::get_root_of;
+/// let root = get_root_of(&[1u8, 1, 2, 3]);
+/// 
+/// assert_eq!(root.frequency, 4);
+/// ```
+#[must_use]
+pub fn get_root_of<T: Hash + Eq + Copy>(symbols: &[T]) -> Box<Node<T>> {
+    try_get_root_of(symbols).unwrap()
+}
+
+/// Gets the Huffman tree for a slice of arbitrary symbols, failing on empty input.
+/// # Arguments
+/// * `symbols` - The symbols of which the tree should be created.
+/// # Returns
+/// A `Result<Box<Node<T>>, HuffmanError>`.
+pub fn try_get_root_of<T: Hash + Eq + Copy>(symbols: &[T]) -> Result<Box<Node<T>>, HuffmanError> {
+    try_get_root_from_frequencies(&get_frequency_of(symbols))
+}
+
+/// Builds the Huffman tree out of already counted symbol frequencies.
+/// # Arguments
+/// * `frequency` - How often each symbol occurs.
+/// # Returns
+/// A `Result<Box<Node<T>>, HuffmanError>`. Fails with `HuffmanError::EmptyInput` if `frequency` is empty.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::try_get_root_from_frequencies;
+/// use std::collections::HashMap;
+/// let mut frequency: HashMap<char, usize> = HashMap::new();
+/// frequency.insert('a', 10);
+/// frequency.insert('b', 5);
+/// 
+/// assert_eq!(try_get_root_from_frequencies(&frequency).unwrap().frequency, 15);
+/// ```
+pub fn try_get_root_from_frequencies<T: Copy>(frequency: &HashMap<T, usize>) -> Result<Box<Node<T>>, HuffmanError> {
+    // The heap only holds frequencies and indices, the nodes themselves wait in `vec_nodes` until they are merged.
+    let mut vec_nodes: Vec<Option<Box<Node<T>>>>           = frequency.iter().map(|x| Some(Node::new_box(Node::new(*(x.1) as u128, Some(*(x.0)))))).collect();
+    let mut heap:      BinaryHeap<Reverse<(u128, usize)>> = vec_nodes.iter().enumerate().map(|(i, x)| Reverse((x.as_ref().unwrap().frequency, i))).collect();
+
+    while heap.len() > 1 {
+        let a: Box<Node<T>>     = vec_nodes[heap.pop().unwrap().0.1].take().unwrap();
+        let b: Box<Node<T>>     = vec_nodes[heap.pop().unwrap().0.1].take().unwrap();
+        let mut c: Box<Node<T>> = Node::new_box(Node::new(a.frequency + b.frequency, None));
+
+        heap.push(Reverse((c.frequency, vec_nodes.len())));
+        c.left  = Some(a);
+        c.right = Some(b);
+        vec_nodes.push(Some(c));
+    }
+
+    let root: Box<Node<T>> = match heap.pop() {
+        Some(Reverse((_, i))) => vec_nodes[i].take().unwrap(),
+        None                  => { return Err(HuffmanError::EmptyInput); }
+    };
+    if root.character.is_none()
+    { return Ok(root); }
+
+    let mut parent: Box<Node<T>> = Node::new_box(Node::new(root.frequency, None));
+    parent.left = Some(root);
+    Ok(parent)
+}
+
+/// The code length limit used by `canonical_encode`. Codes of up to 15 bits keep table-driven decoding fast.
+pub const MAX_CODE_LENGTH: u8 = 15;
+
+/// Gets the length of the code of every symbol in a Huffman tree.
+/// # Arguments
+/// * `root` - The Huffman tree.
+/// # Returns
+/// A `Result<Vec<(T, u8)>, HuffmanError>`. Fails with `HuffmanError::CodeTooLong` if the tree is deeper than 64.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{get_root, code_lengths};
+/// let mut lengths = code_lengths(&get_root("aaaabbc")).unwrap();
+/// lengths.sort();
+/// 
+/// assert_eq!(lengths, vec![('a', 1), ('b', 2), ('c', 2)]);
+/// ```
+pub fn code_lengths<T: Copy>(root: &Node<T>) -> Result<Vec<(T, u8)>, HuffmanError> {
+    let mut lengths: Vec<(T, u8)> = Vec::new();
+    collect_lengths(root, 0, &mut lengths)?;
+    Ok(lengths)
+}
+
+/// Collects the depth of every leaf, which is the length of its code.
+fn collect_lengths<T: Copy>(node: &Node<T>, depth: usize, lengths: &mut Vec<(T, u8)>) -> Result<(), HuffmanError> {
+    if let Some(c) = node.character {
+        if depth > 64
+        { return Err(HuffmanError::CodeTooLong); }
+        lengths.push((c, depth.max(1) as u8));
+        return Ok(());
+    }
+    if let Some(ref l) = node.left
+    { collect_lengths(l, depth + 1, lengths)?; }
+    if let Some(ref r) = node.right
+    { collect_lengths(r, depth + 1, lengths)?; }
+    Ok(())
+}
+
+/// Gets code lengths that never exceed `max_len`, using the package-merge algorithm.
+/// The result is optimal among all codes with this limit and equal to plain Huffman if no code would be longer anyway.
+/// # Arguments
+/// * `frequency` - How often each symbol occurs.
+/// * `max_len` - The maximum code length, `1..=64`.
+/// # Returns
+/// A `Result<Vec<(T, u8)>, HuffmanError>`. Fails with `HuffmanError::EmptyInput` if `frequency` is empty and with
+/// `HuffmanError::CodeTooLong` if `max_len` bits can't give every symbol its own code.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::limited_code_lengths;
+/// use std::collections::HashMap;
+/// let frequency: HashMap<char, usize> = vec![('a', 1), ('b', 1), ('c', 2), ('d', 4), ('e', 8)].into_iter().collect();
+/// let mut lengths = limited_code_lengths(&frequency, 3).unwrap();
+/// lengths.sort();
+/// 
+/// assert_eq!(lengths, vec![('a', 3), ('b', 3), ('c', 3), ('d', 3), ('e', 1)]);
+/// ```
+pub fn limited_code_lengths<T: Ord + Copy>(frequency: &HashMap<T, usize>, max_len: u8) -> Result<Vec<(T, u8)>, HuffmanError> {
+    let mut symbols: Vec<(usize, T)> = frequency.iter().map(|(s, f)| (*f, *s)).collect();
+    symbols.sort_unstable();
+
+    match symbols.len() {
+        0 => { return Err(HuffmanError::EmptyInput); }
+        1 => { return Ok(vec![(symbols[0].1, 1)]); }
+        _ => { }
+    }
+    if !max_len.is_in_range(1, 64) || symbols.len() as u128 > 1u128 << max_len
+    { return Err(HuffmanError::CodeTooLong); }
+
+    // Packages only store the indices of their two halves, leaves have none.
+    let mut arena:  Vec<Option<(usize, usize)>> = vec![None; symbols.len()];
+    let leaves:     Vec<(u128, usize)>          = symbols.iter()
+                                                         .enumerate()
+                                                         .map(|(i, x)| (x.0 as u128, i))
+                                                         .collect();
+    let mut current: Vec<(u128, usize)>         = leaves.clone();
+
+    for _ in 1..max_len {
+        let mut packages: Vec<(u128, usize)> = Vec::with_capacity(current.len() / 2);
+        for pair in current.chunks_exact(2) {
+            arena.push(Some((pair[0].1, pair[1].1)));
+            packages.push((pair[0].0 + pair[1].0, arena.len() - 1));
+        }
+
+        current = Vec::with_capacity(leaves.len() + packages.len());
+        let (mut i, mut j) = (0, 0);
+        while i < leaves.len() || j < packages.len() {
+            if j == packages.len() || (i < leaves.len() && leaves[i].0 <= packages[j].0)
+            { current.push(leaves[i]); i += 1; }
+            else
+            { current.push(packages[j]); j += 1; }
+        }
+    }
+
+    let mut lengths: Vec<u8>    = vec![0; symbols.len()];
+    let mut stack:   Vec<usize> = current.iter()
+                                         .take(2 * symbols.len() - 2)
+                                         .map(|x| x.1)
+                                         .collect();
+    while let Some(item) = stack.pop() {
+        match arena[item] {
+            Some((a, b)) => { stack.push(a); stack.push(b); }
+            None         => { lengths[item] += 1; }
+        }
+    }
+
+    Ok(symbols.iter()
+              .zip(lengths)
+              .map(|(s, l)| (s.1, l))
+              .collect())
+}
+
+/// Assigns canonical Huffman codes to symbols with known code lengths.
+/// Codes are handed out in order of length and then symbol, so the same lengths always result in the same codes.
+/// # Arguments
+/// * `lengths` - The code length of each symbol. Gets sorted into canonical order.
+/// # Returns
+/// A `Result<HashMap<T, BitVec>, HuffmanError>`. Fails with `HuffmanError::CorruptTree` if a length is not in `1..=64`
+/// or the lengths over-subscribe the code space.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{get_root, code_lengths, assign_canonical_codes, huffman_encode};
+/// use bit_vec::BitVec;
+/// let s = "Lorem Ipsum";
+/// let mut lengths = code_lengths(&get_root(s)).unwrap(); // Only these have to be stored.
+/// let codes = assign_canonical_codes(&mut lengths).unwrap();
+/// 
+/// assert_eq!(codes[&lengths[0].0], BitVec::from_elem(lengths[0].1 as usize, false));
+/// let enc = huffman_encode(s, &codes);
+/// ```
+pub fn assign_canonical_codes<T: Hash + Ord + Copy>(lengths: &mut [(T, u8)]) -> Result<HashMap<T, BitVec>, HuffmanError> {
+    lengths.sort_unstable_by_key(|x| (x.1, x.0));
+    let mut res:      HashMap<T, BitVec> = HashMap::with_capacity(lengths.len());
+    let mut code:     u128               = 0;
+    let mut prev_len: u8                 = 0;
+
+    for (i, (c, len)) in lengths.iter().enumerate() {
+        if !len.is_in_range(1, 64)
+        { return Err(HuffmanError::CorruptTree); }
+        if i > 0
+        { code = (code + 1) << (len - prev_len); }
+        if code >> len != 0
+        { return Err(HuffmanError::CorruptTree); }
+        prev_len = *len;
+
+        let mut bits: BitVec = BitVec::with_capacity(*len as usize);
+        for shift in (0..*len).rev() {
+            bits.push((code >> shift) & 1 == 1);
+        }
+        res.insert(*c, bits);
+    }
+    Ok(res)
+}
+
+/// Rebuilds the decoding tree of canonical codes out of their code lengths.
+/// # Arguments
+/// * `lengths` - The code length of each symbol.
+/// # Returns
+/// A `Result<Box<Node<T>>, HuffmanError>`. The frequencies of the nodes are `0`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{tree_from_lengths, decode_symbols};
+/// use bit_vec::BitVec;
+/// let root = tree_from_lengths(&[(b'a', 1), (b'b', 2), (b'c', 2)]).unwrap();
+/// let enc  = BitVec::from_bytes(&[0b0_10_11_0_00]);
+/// 
+/// assert_eq!(decode_symbols(&enc, &root).unwrap(), b"abcaaa".to_vec());
+/// ```
+pub fn tree_from_lengths<T: Hash + Ord + Copy>(lengths: &[(T, u8)]) -> Result<Box<Node<T>>, HuffmanError> {
+    tree_from_codes(&assign_canonical_codes(&mut lengths.to_vec())?)
+}
+
+/// Builds a decoding tree out of prefix-free codes.
+fn tree_from_codes<T: Copy>(codes: &HashMap<T, BitVec>) -> Result<Box<Node<T>>, HuffmanError> {
+    let mut root: Box<Node<T>> = Node::new_box(Node::new(0, None));
+
+    for (c, code) in codes {
+        let mut nodeptr: &mut Box<Node<T>> = &mut root;
+        for b in code {
+            if nodeptr.character.is_some()
+            { return Err(HuffmanError::CorruptTree); }
+            let next = match b {
+                false => &mut nodeptr.left,
+                true  => &mut nodeptr.right,
+            };
+            nodeptr = next.get_or_insert_with(|| Node::new_box(Node::new(0, None)));
+        }
+        if nodeptr.character.is_some() || nodeptr.left.is_some() || nodeptr.right.is_some()
+        { return Err(HuffmanError::CorruptTree); }
+        nodeptr.character = Some(*c);
+    }
+    Ok(root)
+}
+
+/// Encodes a string with canonical codes of at most `MAX_CODE_LENGTH` bits.
+/// Unlike `full_encode`, only the code lengths are needed to decode the result.
+/// # Arguments
+/// * `s` - The string to be encoded.
+/// # Returns
+/// A `Result<(BitVec, Vec<(char, u8)>), HuffmanError>` with the encoded string and the code length of each character.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{canonical_encode, canonical_decode};
+/// let (enc, lengths) = canonical_encode("Lorem Ipsum").unwrap();
+/// 
+/// assert_eq!(canonical_decode(&enc, &lengths).unwrap(), "Lorem Ipsum");
+/// ```
+pub fn canonical_encode(s: &str) -> Result<(BitVec, Vec<(char, u8)>), HuffmanError> {
+    let mut lengths: Vec<(char, u8)>      = limited_code_lengths(&get_frequency(s), MAX_CODE_LENGTH)?;
+    let codes:       HashMap<char, BitVec> = assign_canonical_codes(&mut lengths)?;
+    Ok((try_huffman_encode(s, &codes)?, lengths))
+}
+
+/// Decodes a string encoded with canonical codes.
+/// # Arguments
+/// * `bitvec` - The encoded string.
+/// * `lengths` - The code length of each character.
+/// # Returns
+/// A `Result<String, HuffmanError>`.
+pub fn canonical_decode(bitvec: &BitVec, lengths: &[(char, u8)]) -> Result<String, HuffmanError> {
+    table::DecodeTable::from_lengths(lengths)?.decode_string(bitvec)
+}
+
+/// Writes the encoded message into a single `.hlr` container file.
+/// # Arguments
+/// * `path` - The path to be written to, without the file extension.
+/// * `bitvec` - The Huffman encoded message.
+/// * `root` - The Huffman tree.
+/// # Returns
+/// Nothing. Writes to `path.hlr`, see `write_to` for the layout of the container.
+/// # Panics
+/// Panics if the file could not be written. Use `try_write_to_file` to handle this case.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{Encode, write_to_file};
+/// let s: &str = "Lorem Ipsum";
+/// 
+/// let enc = s.full_encode(); // Encodes the String s into enc.
+/// let path: String = std::env::temp_dir().join("lib_rapid_test").to_string_lossy().into_owned();
+/// write_to_file(path.clone(), &enc.0, &enc.1);
+/// # std::fs::remove_file(path + ".hlr").unwrap();
+/// ```
+pub fn write_to_file(path: String, bitvec: &BitVec, root: &Box<Node>) {
+    try_write_to_file(path, bitvec, root).unwrap();
+}
+
+/// Writes the encoded message into a single `.hlr` container file.
+/// # Arguments
+/// * `path` - The path to be written to, without the file extension.
+/// * `bitvec` - The Huffman encoded message.
+/// * `root` - The Huffman tree.
+/// # Returns
+/// A `Result<(), HuffmanError>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{Encode, try_write_to_file, try_read_from_file};
+/// let enc = "Lorem Ipsum".full_encode();
+/// 
+/// let path: String = std::env::temp_dir().join("lib_rapid_test_try").to_string_lossy().into_owned();
+/// assert!(try_write_to_file(path.clone(), &enc.0, &enc.1).is_ok());
+/// assert_eq!(try_read_from_file(path.clone()).unwrap(), "Lorem Ipsum");
+/// assert!(try_read_from_file("does_not_exist".to_string()).is_err());
+/// std::fs::remove_file(path + ".hlr").unwrap();
+/// ```
+pub fn try_write_to_file(path: String, bitvec: &BitVec, root: &Node) -> Result<(), HuffmanError> {
+    let mut file: File = File::create(path + ".hlr")?;
+    write_to(&mut file, bitvec, root)
+}
+
+/// Reads and decodes the message stored in a `.hlr` container file.
+/// # Arguments
+/// * `path` - The path to be read from, without the file extension.
+/// # Returns
+/// A String containing the decoded message.
+/// # Panics
+/// Panics if the file could not be read or is not a valid container. Use `try_read_from_file` to handle this case.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{Encode, Decode, write_to_file, read_from_file};
+/// let s: &str = "Lorem Ipsum123123123";
+/// 
+/// let enc = s.full_encode(); // Encodes the String s into enc.
+/// let dec = enc.full_decode(); // Decodes the BitVec which was created by the last line.
+/// let path: String = std::env::temp_dir().join("lib_rapid_test_read").to_string_lossy().into_owned();
+/// write_to_file(path.clone(), &enc.0, &enc.1);
+/// 
+/// let dec_written = read_from_file(path.clone());
+/// assert_eq!(dec_written, "Lorem Ipsum123123123");
+/// std::fs::remove_file(path + ".hlr").unwrap();
+/// ```
+#[must_use]
+pub fn read_from_file(path: String) -> String {
+    try_read_from_file(path).unwrap()
+}
+
+/// Reads and decodes the message stored in a `.hlr` container file.
+/// # Arguments
+/// * `path` - The path to be read from, without the file extension.
+/// # Returns
+/// A `Result<String, HuffmanError>`.
+pub fn try_read_from_file(path: String) -> Result<String, HuffmanError> {
+    let mut file: File = File::open(path + ".hlr")?;
+    read_from(&mut file)
+}
+
+/// The magic number every LibRapid Huffman container starts with.
+pub const MAGIC: [u8; 4] = *b"LRHF";
+/// The version of the container format written by `write_to`.
+pub const FORMAT_VERSION: u8 = 1;
+/// Symbol kind byte for `char` symbols, stored as UTF-32 code points.
+const SYMBOL_CHAR: u8 = 0;
+/// Magic (4) + version (1) + symbol kind (1) + bit length (8) + symbol count (4).
+const HEADER_LEN: usize = 18;
+/// The trailing CRC-32.
+const CHECKSUM_LEN: usize = 4;
+
+/// Writes a Huffman encoded message as a self-describing container into any `Write`.
+/// # Arguments
+/// * `writer` - The destination.
+/// * `bitvec` - The Huffman encoded message.
+/// * `root` - The Huffman tree `bitvec` was encoded with.
+/// # Returns
+/// A `Result<(), HuffmanError>`.
+/// # Format
+/// All integers are little endian.
+/// * `[u8; 4]` - The magic number `LRHF`.
+/// * `u8` - The format version.
+/// * `u8` - The symbol kind (`0` for `char`).
+/// * `u64` - The exact number of payload bits.
+/// * `u32` - The number of symbols in the code table.
+/// * `(u32, u8)` per symbol - The code point and its code length, in canonical order.
+/// * `[u8]` - The payload, re-encoded with the canonical codes and padded to a full byte.
+/// * `u32` - A CRC-32 over everything before it.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{Encode, write_to, read_from};
+/// let enc = "Lorem Ipsum".full_encode();
+/// let mut container: Vec<u8> = Vec::new();
+/// 
+/// write_to(&mut container, &enc.0, &enc.1).unwrap();
+/// assert_eq!(read_from(&mut container.as_slice()).unwrap(), "Lorem Ipsum");
+/// ```
+pub fn write_to<W: Write>(writer: &mut W, bitvec: &BitVec, root: &Node) -> Result<(), HuffmanError> {
+    let mut lengths: Vec<(char, u8)> = code_lengths(root)?;
+    let codes: HashMap<char, BitVec> = assign_canonical_codes(&mut lengths)?;
+    let payload: BitVec              = transcode(bitvec, root, &codes)?;
+
+    let mut out: Vec<u8> = Vec::with_capacity(HEADER_LEN + lengths.len() * 5 + payload.len() / 8 + 1 + CHECKSUM_LEN);
+    out.extend_from_slice(&MAGIC);
+    out.push(FORMAT_VERSION);
+    out.push(SYMBOL_CHAR);
+    out.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    out.extend_from_slice(&(lengths.len() as u32).to_le_bytes());
+    for (c, len) in &lengths {
+        out.extend_from_slice(&(*c as u32).to_le_bytes());
+        out.push(*len);
+    }
+    out.extend_from_slice(&payload.to_bytes());
+    let checksum: u32 = crc32(&out);
+    out.extend_from_slice(&checksum.to_le_bytes());
+
+    writer.write_all(&out)?;
+    Ok(())
+}
+
+/// Reads and decodes a container written by `write_to` from any `Read`.
+/// # Arguments
+/// * `reader` - The source.
+/// # Returns
+/// A `Result<String, HuffmanError>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{Encode, write_to, read_from, HuffmanError};
+/// let enc = "Lorem Ipsum".full_encode();
+/// let mut container: Vec<u8> = Vec::new();
+/// write_to(&mut container, &enc.0, &enc.1).unwrap();
+/// 
+/// container[20] ^= 0xFF; // Corrupt the container.
+/// assert!(matches!(read_from(&mut container.as_slice()), Err(HuffmanError::ChecksumMismatch)));
+/// ```
+pub fn read_from<R: Read>(reader: &mut R) -> Result<String, HuffmanError> {
+    let mut data: Vec<u8> = Vec::new();
+    reader.read_to_end(&mut data)?;
+
+    if data.len() < HEADER_LEN + CHECKSUM_LEN || data[0..4] != MAGIC
+    { return Err(HuffmanError::InvalidContainer("Not a LibRapid Huffman container.")); }
+    if data[4] != FORMAT_VERSION
+    { return Err(HuffmanError::InvalidContainer("Unsupported container version.")); }
+    if data[5] != SYMBOL_CHAR
+    { return Err(HuffmanError::InvalidContainer("Unsupported symbol kind.")); }
+
+    let (body, checksum) = data.split_at(data.len() - CHECKSUM_LEN);
+    if crc32(body) != u32::from_le_bytes([checksum[0], checksum[1], checksum[2], checksum[3]])
+    { return Err(HuffmanError::ChecksumMismatch); }
+
+    let bit_len: u64   = u64::from_le_bytes(body[6..14].try_into().unwrap());
+    let symbols: usize = u32::from_le_bytes(body[14..18].try_into().unwrap()) as usize;
+    let table_end      = symbols.checked_mul(5)
+                                .and_then(|x| x.checked_add(HEADER_LEN))
+                                .filter(|x| *x <= body.len())
+                                .ok_or(HuffmanError::InvalidContainer("Truncated code table."))?;
+
+    let mut lengths: Vec<(char, u8)> = Vec::with_capacity(symbols);
+    for entry in body[HEADER_LEN..table_end].chunks(5) {
+        let c: char = char::from_u32(u32::from_le_bytes(entry[0..4].try_into().unwrap()))
+                      .ok_or(HuffmanError::CorruptTree)?;
+        lengths.push((c, entry[4]));
+    }
+
+    let payload: &[u8] = &body[table_end..];
+    if (payload.len() as u64) != bit_len.div_ceil(8)
+    { return Err(HuffmanError::InvalidContainer("Payload length does not match the bit length.")); }
+
+    let mut bitvec: BitVec = BitVec::from_bytes(payload);
+    bitvec.truncate(bit_len as usize);
+
+    table::DecodeTable::from_lengths(&lengths)?.decode_string(&bitvec)
+}
+
+/// Re-encodes a bitstream from the codes of `root` to `codes`.
+fn transcode(bitvec: &BitVec, root: &Node, codes: &HashMap<char, BitVec>) -> Result<BitVec, HuffmanError> {
+    let mut res:     BitVec     = BitVec::with_capacity(bitvec.len());
+    let mut nodeptr: &Node = root;
+
+    for b in bitvec {
+        let next = match b {
+            false => &nodeptr.left,
+            true  => &nodeptr.right,
+        };
+        nodeptr = next.as_ref()
+                      .ok_or(HuffmanError::CorruptTree)?;
+
+        if let Some(c) = nodeptr.character {
+            res.extend(codes[&c].iter());
+            nodeptr = root;
+        }
+    }
+    if !std::ptr::eq(nodeptr, root)
+    { return Err(HuffmanError::TruncatedBitstream); }
+    Ok(res)
 }
\ No newline at end of file
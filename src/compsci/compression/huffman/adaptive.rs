@@ -0,0 +1,514 @@
+//! Adaptive Huffman coding (FGK algorithm), which needs only one pass over the data.
+//! Encoder and decoder start with the same empty tree and update it after every symbol, so no tree is transmitted.
+//! A byte that hasn't been seen yet is sent as the code of the NYT ("not yet transmitted") leaf followed by the byte itself.
+//! # Stream format
+//! * `[u8; 4]` - The magic number `LRHA`.
+//! * `u8` - The format version.
+//! * The adaptive codes of all bytes and of an end-of-stream symbol, padded to a full byte.
+//!   Flushing the writer inserts a flush symbol padded to a full byte, which the decoder skips along with its padding.
+use std::io::{self, Read, Write};
+use bit_vec::BitVec;
+
+use super::{Node, HuffmanError};
+
+/// The magic number every LibRapid adaptive Huffman stream starts with.
+pub const ADAPTIVE_MAGIC: [u8; 4] = *b"LRHA";
+/// The version of the stream format written by `AdaptiveHuffmanWriter`.
+pub const ADAPTIVE_VERSION: u8 = 1;
+/// The 256 bytes, the end-of-stream symbol and the flush symbol.
+const SYMBOLS:     usize = 258;
+const END:         usize = 256;
+const FLUSH:       usize = 257;
+/// The number of bits a symbol is sent with the first time.
+const ESCAPE_BITS: usize = 9;
+/// The number of the root. Every symbol adds two nodes below the NYT leaf.
+const MAX_NUMBER:  usize = 2 * SYMBOLS;
+const NONE:        usize = usize::MAX;
+
+#[derive(Clone, Debug)]
+struct Slot {
+    weight: u64,
+    parent: usize,
+    left:   usize,
+    right:  usize,
+    symbol: usize,
+    /// The position in the sibling order. Weights never decrease with increasing numbers.
+    number: usize,
+}
+
+impl Slot {
+    const fn new(parent: usize, symbol: usize, number: usize) -> Slot {
+        Slot { weight: 0, parent, left: NONE, right: NONE, symbol, number }
+    }
+}
+
+/// The tree shared by the adaptive encoder and decoder.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::adaptive::AdaptiveTree;
+/// use bit_vec::BitVec;
+/// let mut tree = AdaptiveTree::new();
+/// let mut bits = BitVec::new();
+/// for b in b"abracadabra" {
+///     tree.encode(*b, &mut bits);
+/// }
+///
+/// // 'a' is the most frequent byte by now, so it has the shortest code.
+/// assert_eq!(tree.code_of(b'a').unwrap().len(), 1);
+/// assert_eq!(tree.to_tree().frequency, 11);
+/// ```
+#[derive(Clone, Debug)]
+pub struct AdaptiveTree {
+    nodes:     Vec<Slot>,
+    by_number: Vec<usize>,
+    leaves:    Vec<usize>,
+    nyt:       usize,
+}
+
+impl AdaptiveTree {
+    /// The index of the root, which never moves.
+    const ROOT: usize = 0;
+
+    /// Creates a new `AdaptiveTree`, which only consists of the NYT leaf.
+    /// # Returns
+    /// A new `AdaptiveTree`.
+    #[must_use]
+    pub fn new() -> AdaptiveTree {
+        let mut by_number: Vec<usize> = vec![NONE; MAX_NUMBER + 1];
+        by_number[MAX_NUMBER] = AdaptiveTree::ROOT;
+        AdaptiveTree { nodes:     vec![Slot::new(NONE, NONE, MAX_NUMBER)],
+                       by_number,
+                       leaves:    vec![NONE; SYMBOLS],
+                       nyt:       AdaptiveTree::ROOT }
+    }
+    /// Encodes a byte and updates the tree.
+    /// # Arguments
+    /// * `byte` - The byte to be encoded.
+    /// * `out` - The `BitVec` the code is appended to.
+    pub fn encode(&mut self, byte: u8, out: &mut BitVec) {
+        self.encode_symbol(byte as usize, out);
+    }
+    /// Get the current code of a byte.
+    /// # Arguments
+    /// * `byte` - The byte.
+    /// # Returns
+    /// `Some(BitVec)` if the byte has been coded before, `None` otherwise.
+    #[must_use]
+    pub fn code_of(&self, byte: u8) -> Option<BitVec> {
+        match self.leaves[byte as usize] {
+            NONE => None,
+            leaf => Some(self.path(leaf)),
+        }
+    }
+    /// Creates a snapshot of the tree out of `Node`s, e.g. to print it or to hand it to `assign_codes`.
+    /// The NYT, end-of-stream and flush leaves have no character.
+    /// # Returns
+    /// A `Box<Node<u8>>` whose frequencies are the weights of the adaptive tree.
+    #[must_use]
+    pub fn to_tree(&self) -> Box<Node<u8>> {
+        self.snapshot(AdaptiveTree::ROOT)
+    }
+
+    fn snapshot(&self, index: usize) -> Box<Node<u8>> {
+        let slot: &Slot = &self.nodes[index];
+        Box::new(Node { character: if slot.symbol < 256 { Some(slot.symbol as u8) } else { None },
+                        frequency: slot.weight as u128,
+                        left:      if slot.left == NONE { None } else { Some(self.snapshot(slot.left)) },
+                        right:     if slot.right == NONE { None } else { Some(self.snapshot(slot.right)) } })
+    }
+
+    fn encode_symbol(&mut self, symbol: usize, out: &mut BitVec) {
+        let leaf: usize = self.leaves[symbol];
+        if leaf != NONE {
+            out.extend(self.path(leaf).iter());
+            self.update(leaf);
+            return;
+        }
+
+        out.extend(self.path(self.nyt).iter());
+        for i in (0..ESCAPE_BITS).rev()
+        { out.push((symbol >> i) & 1 == 1); }
+        let leaf: usize = self.add(symbol);
+        self.update(leaf);
+    }
+
+    /// Decodes the next symbol out of `bits` and updates the tree.
+    fn decode_symbol<F: FnMut() -> Result<bool, HuffmanError>>(&mut self, mut next_bit: F) -> Result<usize, HuffmanError> {
+        let mut node: usize = AdaptiveTree::ROOT;
+        while self.nodes[node].left != NONE {
+            node = if next_bit()? { self.nodes[node].right } else { self.nodes[node].left };
+        }
+
+        if node == self.nyt {
+            let mut symbol: usize = 0;
+            for _ in 0..ESCAPE_BITS
+            { symbol = (symbol << 1) | next_bit()? as usize; }
+            // A symbol is escaped only once.
+            if symbol >= SYMBOLS || self.leaves[symbol] != NONE
+            { return Err(HuffmanError::CorruptTree); }
+            node = self.add(symbol);
+        }
+        let symbol: usize = self.nodes[node].symbol;
+        self.update(node);
+        Ok(symbol)
+    }
+
+    /// The code of a node: the path from the root, `1` meaning right.
+    fn path(&self, node: usize) -> BitVec {
+        let mut res:  BitVec = BitVec::new();
+        let mut node: usize  = node;
+        while self.nodes[node].parent != NONE {
+            let parent: usize = self.nodes[node].parent;
+            res.push(self.nodes[parent].right == node);
+            node = parent;
+        }
+        BitVec::from_fn(res.len(), |i| res[res.len() - 1 - i])
+    }
+
+    /// Splits the NYT leaf into a new NYT leaf and a leaf for `symbol`. Returns the new leaf.
+    fn add(&mut self, symbol: usize) -> usize {
+        let old:    usize = self.nyt;
+        let number: usize = self.nodes[old].number;
+        let nyt:    usize = self.nodes.len();
+        let leaf:   usize = nyt + 1;
+
+        self.nodes.push(Slot::new(old, NONE, number - 2));
+        self.nodes.push(Slot::new(old, symbol, number - 1));
+        self.nodes[old].left       = nyt;
+        self.nodes[old].right      = leaf;
+        self.by_number[number - 2] = nyt;
+        self.by_number[number - 1] = leaf;
+        self.leaves[symbol]        = leaf;
+        self.nyt                   = nyt;
+        leaf
+    }
+
+    /// Increments the weights from `node` up to the root, swapping nodes to keep the sibling property.
+    fn update(&mut self, node: usize) {
+        let mut node: usize = node;
+        while node != NONE {
+            let weight:     u64   = self.nodes[node].weight;
+            let mut leader: usize = self.nodes[node].number;
+            while leader < MAX_NUMBER && self.nodes[self.by_number[leader + 1]].weight == weight
+            { leader += 1; }
+
+            let leader: usize = self.by_number[leader];
+            if leader != node && leader != self.nodes[node].parent
+            { self.swap(node, leader); }
+            self.nodes[node].weight += 1;
+            node = self.nodes[node].parent;
+        }
+    }
+
+    /// Exchanges the positions of two subtrees.
+    fn swap(&mut self, a: usize, b: usize) {
+        let (pa, pb) = (self.nodes[a].parent, self.nodes[b].parent);
+        if pa == pb {
+            let p: &mut Slot = &mut self.nodes[pa];
+            std::mem::swap(&mut p.left, &mut p.right);
+        }
+        else {
+            self.replace_child(pa, a, b);
+            self.replace_child(pb, b, a);
+            self.nodes[a].parent = pb;
+            self.nodes[b].parent = pa;
+        }
+
+        let (na, nb) = (self.nodes[a].number, self.nodes[b].number);
+        self.nodes[a].number = nb;
+        self.nodes[b].number = na;
+        self.by_number[na]   = b;
+        self.by_number[nb]   = a;
+    }
+
+    fn replace_child(&mut self, parent: usize, old: usize, new: usize) {
+        let p: &mut Slot = &mut self.nodes[parent];
+        if p.left == old
+        { p.left = new; }
+        else
+        { p.right = new; }
+    }
+}
+
+impl Default for AdaptiveTree {
+    fn default() -> AdaptiveTree {
+        AdaptiveTree::new()
+    }
+}
+
+/// Encodes bytes with adaptive Huffman coding in a single pass.
+/// # Arguments
+/// * `data` - The bytes to be encoded.
+/// # Returns
+/// A `BitVec`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::adaptive::{adaptive_encode, adaptive_decode};
+/// let data = "Lorem ipsum dolor sit amet, consectetur adipiscing elit.".repeat(20);
+/// let enc  = adaptive_encode(data.as_bytes());
+///
+/// assert!(enc.len() < data.len() * 5);
+/// assert_eq!(adaptive_decode(&enc).unwrap(), data.as_bytes());
+/// ```
+#[must_use]
+pub fn adaptive_encode(data: &[u8]) -> BitVec {
+    let mut tree: AdaptiveTree = AdaptiveTree::new();
+    let mut res:  BitVec       = BitVec::with_capacity(data.len() * 5);
+    for b in data
+    { tree.encode(*b, &mut res); }
+    res
+}
+
+/// Decodes bytes encoded by `adaptive_encode`.
+/// # Arguments
+/// * `bitvec` - The encoded bytes.
+/// # Returns
+/// A `Result<Vec<u8>, HuffmanError>`. Fails with `HuffmanError::TruncatedBitstream` if the last code is incomplete.
+pub fn adaptive_decode(bitvec: &BitVec) -> Result<Vec<u8>, HuffmanError> {
+    let mut tree: AdaptiveTree = AdaptiveTree::new();
+    let mut res:  Vec<u8>      = Vec::with_capacity(bitvec.len() / 5);
+    let mut bits               = bitvec.iter();
+
+    while bits.len() > 0 {
+        match tree.decode_symbol(|| bits.next().ok_or(HuffmanError::TruncatedBitstream))? {
+            END | FLUSH => { return Err(HuffmanError::CorruptTree); }
+            s           => { res.push(s as u8); }
+        }
+    }
+    Ok(res)
+}
+
+/// Compresses everything written to it with adaptive Huffman coding into an underlying `Write`.
+/// Complete bytes are passed on right away. `flush` passes on the rest as well, so everything written so far can be decoded.
+/// The stream is completed by `finish` or, ignoring errors, when the writer is dropped.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::adaptive::{AdaptiveHuffmanWriter, AdaptiveHuffmanReader};
+/// use std::io::{Read, Write};
+///
+/// let mut writer = AdaptiveHuffmanWriter::new(Vec::new());
+/// for reading in ["t=1 temp=20.5\n", "t=2 temp=20.6\n", "t=3 temp=20.6\n"] {
+///     writer.write_all(reading.as_bytes()).unwrap();
+/// }
+/// let compressed: Vec<u8> = writer.finish().unwrap();
+///
+/// let mut res = String::new();
+/// AdaptiveHuffmanReader::new(compressed.as_slice()).read_to_string(&mut res).unwrap();
+/// assert_eq!(res, "t=1 temp=20.5\nt=2 temp=20.6\nt=3 temp=20.6\n");
+/// ```
+/// Every record can be decoded right after it has been flushed, e.g. from a non-blocking socket:
+/// ```
+/// use lib_rapid::compsci::compression::huffman::adaptive::{AdaptiveHuffmanWriter, AdaptiveHuffmanReader};
+/// use std::{cell::RefCell, collections::VecDeque, io::{self, Read, Write}, rc::Rc};
+///
+/// // A pipe which fails with `WouldBlock` while it is empty.
+/// #[derive(Clone, Default)]
+/// struct Pipe(Rc<RefCell<VecDeque<u8>>>);
+/// impl Write for Pipe {
+///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.borrow_mut().extend(buf); Ok(buf.len()) }
+///     fn flush(&mut self) -> io::Result<()> { Ok(()) }
+/// }
+/// impl Read for Pipe {
+///     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+///         let mut queue = self.0.borrow_mut();
+///         if queue.is_empty()
+///         { return Err(io::ErrorKind::WouldBlock.into()); }
+///         let n: usize = buf.len().min(queue.len());
+///         for (b, q) in buf.iter_mut().zip(queue.drain(..n))
+///         { *b = q; }
+///         Ok(n)
+///     }
+/// }
+///
+/// let pipe: Pipe = Pipe::default();
+/// let mut writer = AdaptiveHuffmanWriter::new(pipe.clone());
+/// let mut reader = AdaptiveHuffmanReader::new(pipe);
+/// let mut buf: [u8; 64] = [0; 64];
+/// for reading in vec!["t=1 temp=20.5\n", "t=2 temp=20.6\n", "t=3 temp=20.6\n"] {
+///     writer.write_all(reading.as_bytes()).unwrap();
+///     writer.flush().unwrap();
+///
+///     let mut res: Vec<u8> = Vec::new();
+///     let err: io::Error   = loop {
+///         match reader.read(&mut buf) {
+///             Ok(n)  => { res.extend_from_slice(&buf[..n]); }
+///             Err(e) => { break e; }
+///         }
+///     };
+///     assert_eq!(res, reading.as_bytes());
+///     assert_eq!(err.kind(), io::ErrorKind::WouldBlock); // The pipe is drained, no bits are stuck in the writer.
+/// }
+/// ```
+pub struct AdaptiveHuffmanWriter<W: Write> {
+    inner:          Option<W>,
+    tree:           AdaptiveTree,
+    pending:        BitVec,
+    header_written: bool,
+}
+
+impl<W: Write> AdaptiveHuffmanWriter<W> {
+    /// Creates a new `AdaptiveHuffmanWriter`.
+    /// # Arguments
+    /// * `inner` - The destination of the compressed stream.
+    /// # Returns
+    /// A new `AdaptiveHuffmanWriter`.
+    #[must_use]
+    pub fn new(inner: W) -> AdaptiveHuffmanWriter<W> {
+        AdaptiveHuffmanWriter { inner:          Some(inner),
+                                tree:           AdaptiveTree::new(),
+                                pending:        BitVec::new(),
+                                header_written: false }
+    }
+    /// Ends the stream and returns the underlying writer.
+    /// # Returns
+    /// A `std::io::Result<W>`.
+    pub fn finish(mut self) -> io::Result<W> {
+        let res:   io::Result<()> = self.write_end();
+        let inner: W              = self.inner.take().unwrap();
+        res.map(|_| inner)
+    }
+
+    fn write_end(&mut self) -> io::Result<()> {
+        self.write_aligned(END)?;
+        self.inner.as_mut().unwrap().flush()
+    }
+
+    /// Writes a symbol and pads it to a full byte, so nothing stays pending.
+    fn write_aligned(&mut self, symbol: usize) -> io::Result<()> {
+        self.tree.encode_symbol(symbol, &mut self.pending);
+        let padding: usize = (8 - self.pending.len() % 8) % 8;
+        self.pending.grow(padding, false);
+        self.write_pending()
+    }
+
+    /// Writes all complete bytes and keeps the remaining bits.
+    fn write_pending(&mut self) -> io::Result<()> {
+        let inner: &mut W = self.inner.as_mut().unwrap();
+        if !self.header_written {
+            inner.write_all(&ADAPTIVE_MAGIC)?;
+            inner.write_all(&[ADAPTIVE_VERSION])?;
+            self.header_written = true;
+        }
+
+        let full:  usize   = self.pending.len() / 8;
+        let bytes: Vec<u8> = self.pending.to_bytes();
+        inner.write_all(&bytes[..full])?;
+        self.pending = BitVec::from_fn(self.pending.len() - full * 8, |i| self.pending[full * 8 + i]);
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for AdaptiveHuffmanWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for b in buf
+        { self.tree.encode(*b, &mut self.pending); }
+        self.write_pending()?;
+        Ok(buf.len())
+    }
+    /// Passes on everything written so far and flushes the underlying writer.
+    /// The bits of an incomplete byte are completed by a flush symbol and padding, which costs up to 2 bytes per flush.
+    fn flush(&mut self) -> io::Result<()> {
+        if self.pending.is_empty()
+        { self.write_pending()?; }
+        else
+        { self.write_aligned(FLUSH)?; }
+        self.inner.as_mut().unwrap().flush()
+    }
+}
+
+impl<W: Write> Drop for AdaptiveHuffmanWriter<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some()
+        { let _ = self.write_end(); }
+    }
+}
+
+/// Decompresses a stream written by `AdaptiveHuffmanWriter` from an underlying `Read`.
+/// Reads one byte at a time and never past the end of the stream, so wrap unbuffered sources in a `BufReader`.
+/// `read` returns as soon as the bytes read so far are decoded, so it doesn't wait for more data on live sources.
+/// If the source fails in the middle of a symbol, e.g. with `WouldBlock`, the symbol is decoded again by the next call.
+pub struct AdaptiveHuffmanReader<R: Read> {
+    inner:       R,
+    tree:        AdaptiveTree,
+    input:       Vec<u8>,
+    pos:         usize,
+    header_read: bool,
+    finished:    bool,
+}
+
+impl<R: Read> AdaptiveHuffmanReader<R> {
+    /// Creates a new `AdaptiveHuffmanReader`.
+    /// # Arguments
+    /// * `inner` - The source of the compressed stream.
+    /// # Returns
+    /// A new `AdaptiveHuffmanReader`.
+    #[must_use]
+    pub fn new(inner: R) -> AdaptiveHuffmanReader<R> {
+        AdaptiveHuffmanReader { inner, tree: AdaptiveTree::new(), input: Vec::new(), pos: 0, header_read: false, finished: false }
+    }
+    /// Returns the underlying reader.
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn read_header(&mut self) -> Result<(), HuffmanError> {
+        // Collected in `input` like the symbols, so an error in between doesn't lose any bytes.
+        while self.input.len() < 5 {
+            let mut b: [u8; 1] = [0];
+            self.inner.read_exact(&mut b)?;
+            self.input.push(b[0]);
+        }
+        if self.input[0..4] != ADAPTIVE_MAGIC
+        { return Err(HuffmanError::InvalidContainer("Not a LibRapid adaptive Huffman stream.")); }
+        if self.input[4] != ADAPTIVE_VERSION
+        { return Err(HuffmanError::InvalidContainer("Unsupported stream version.")); }
+        self.input.clear();
+        self.header_read = true;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for AdaptiveHuffmanReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if !self.header_read
+        { self.read_header()?; }
+
+        let mut n: usize = 0;
+        while n < buf.len() && !self.finished {
+            // Don't wait for another input byte once there is something to return.
+            if n > 0 && self.pos == self.input.len() * 8
+            { break; }
+
+            // `input` keeps the bytes of an unfinished symbol, and the tree only changes once a symbol is complete.
+            let (inner, input) = (&mut self.inner, &mut self.input);
+            let mut pos: usize = self.pos;
+            let res = self.tree.decode_symbol(|| {
+                if pos == input.len() * 8 {
+                    let mut b: [u8; 1] = [0];
+                    inner.read_exact(&mut b)?;
+                    input.push(b[0]);
+                }
+                pos += 1;
+                Ok((input[(pos - 1) / 8] >> (7 - (pos - 1) % 8)) & 1 == 1)
+            });
+            let symbol: usize = match res {
+                Ok(s)           => s,
+                // The error comes up again with the next call.
+                Err(_) if n > 0 => { break; }
+                Err(e)          => { return Err(e.into()); }
+            };
+
+            self.pos = pos;
+            match symbol {
+                END   => { self.finished = true; }
+                FLUSH => { self.pos = self.pos.div_ceil(8) * 8; }
+                s     => { buf[n] = s as u8; n += 1; }
+            }
+            let used: usize = self.pos / 8;
+            self.input.drain(..used);
+            self.pos -= used * 8;
+        }
+        Ok(n)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,364 @@
+//! Multithreaded Huffman compression of large inputs, available with the `parallel` feature.
+//! The input is split into independent blocks which are compressed on all cores.
+//! A block index at the end of the stream lets decompression run in parallel too, or seek to a single block.
+//! # Format
+//! All integers are little endian.
+//! * `[u8; 4]` - The magic number `LRHP`.
+//! * `u8` - The format version.
+//! * `u32` - The block size. Every block but the last one contains exactly this many bytes.
+//! * The blocks, each encoded like a block of `stream::HuffmanWriter`.
+//! * The index:
+//!     * `u64` - The number of blocks.
+//!     * `u64` per block - The offset of the block from the start of the stream.
+//! * `u64` - The length of the uncompressed data.
+//! * `u64` - The offset of the index.
+//! * `[u8; 4]` - The magic number `LRHP` again.
+use std::convert::TryInto;
+use std::io::{self, Read, Write, Seek, SeekFrom, Cursor};
+use std::ops::Range;
+use std::thread;
+
+use super::HuffmanError;
+use super::stream::{MAX_BLOCK_SIZE, encode_block, decode_block};
+
+/// The magic number every LibRapid parallel Huffman stream starts and ends with.
+pub const PARALLEL_MAGIC: [u8; 4] = *b"LRHP";
+/// The version of the format written by `compress_to`.
+pub const PARALLEL_VERSION: u8 = 1;
+/// The block size used by `ParallelConfig::new_default`.
+pub const DEFAULT_PARALLEL_BLOCK_SIZE: usize = 1 << 20;
+const HEADER_LEN:  u64 = 9;
+const TRAILER_LEN: u64 = 20;
+
+/// The settings of the parallel compressor.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ParallelConfig {
+    block_size: usize,
+    threads:    usize,
+}
+
+impl ParallelConfig {
+    /// Creates a new `ParallelConfig`.
+    /// Bigger blocks compress slightly better, smaller blocks allow finer seeking.
+    /// At most `block_size * threads` bytes of input are held in memory.
+    /// # Arguments
+    /// * `block_size` - The number of bytes per block.
+    /// * `threads` - The number of threads. `0` uses all available cores.
+    /// # Returns
+    /// A new `ParallelConfig`.
+    /// # Panics
+    /// Panics if `block_size` is `0` or greater than `stream::MAX_BLOCK_SIZE`.
+    #[must_use]
+    pub const fn new(block_size: usize, threads: usize) -> ParallelConfig {
+        if block_size == 0 || block_size > MAX_BLOCK_SIZE
+        { panic!("The block size must be in the range of 1 to MAX_BLOCK_SIZE."); }
+        ParallelConfig { block_size, threads }
+    }
+    /// Creates a new `ParallelConfig` with blocks of `DEFAULT_PARALLEL_BLOCK_SIZE` bytes, using all available cores.
+    /// # Returns
+    /// A new `ParallelConfig`.
+    #[must_use]
+    pub const fn new_default() -> ParallelConfig {
+        ParallelConfig::new(DEFAULT_PARALLEL_BLOCK_SIZE, 0)
+    }
+    /// Get the number of bytes per block.
+    #[must_use]
+    pub const fn block_size(&self) -> usize {
+        self.block_size
+    }
+    /// Get the number of threads that will be used.
+    #[must_use]
+    pub fn threads(&self) -> usize {
+        resolve_threads(self.threads)
+    }
+}
+
+/// The position of every block in a parallel Huffman stream.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::parallel::{compress, ParallelConfig, BlockIndex, decompress_block};
+/// use std::io::Cursor;
+/// let data: Vec<u8> = (0..10_000u32).map(|x| (x / 100) as u8).collect();
+/// let mut compressed = Cursor::new(compress(&data, &ParallelConfig::new(4096, 2)));
+///
+/// let index = BlockIndex::read_from(&mut compressed).unwrap();
+/// assert_eq!(index.block_count(), 3);
+///
+/// // Only the block containing byte 9000 is read and decoded.
+/// let block = index.block_of(9000).unwrap();
+/// let bytes = decompress_block(&mut compressed, &index, block).unwrap();
+/// assert_eq!(bytes[9000 - index.block_range(block).start as usize], 90);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BlockIndex {
+    block_size:   usize,
+    len:          u64,
+    offsets:      Vec<u64>,
+    index_offset: u64,
+}
+
+impl BlockIndex {
+    /// Reads the index of a parallel Huffman stream. The stream has to span the whole reader.
+    /// # Arguments
+    /// * `reader` - The compressed stream.
+    /// # Returns
+    /// A `Result<BlockIndex, HuffmanError>`.
+    pub fn read_from<R: Read + Seek>(reader: &mut R) -> Result<BlockIndex, HuffmanError> {
+        let end: u64 = reader.seek(SeekFrom::End(0))?;
+        if end < HEADER_LEN + TRAILER_LEN + 8
+        { return Err(HuffmanError::InvalidContainer("Not a LibRapid parallel Huffman stream.")); }
+
+        let mut header: [u8; HEADER_LEN as usize] = [0; HEADER_LEN as usize];
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut header)?;
+        let mut trailer: [u8; TRAILER_LEN as usize] = [0; TRAILER_LEN as usize];
+        reader.seek(SeekFrom::Start(end - TRAILER_LEN))?;
+        reader.read_exact(&mut trailer)?;
+
+        if header[0..4] != PARALLEL_MAGIC || trailer[16..20] != PARALLEL_MAGIC
+        { return Err(HuffmanError::InvalidContainer("Not a LibRapid parallel Huffman stream.")); }
+        if header[4] != PARALLEL_VERSION
+        { return Err(HuffmanError::InvalidContainer("Unsupported stream version.")); }
+
+        let block_size:   usize = u32::from_le_bytes(header[5..9].try_into().unwrap()) as usize;
+        let len:          u64   = u64::from_le_bytes(trailer[0..8].try_into().unwrap());
+        let index_offset: u64   = u64::from_le_bytes(trailer[8..16].try_into().unwrap());
+        if block_size == 0 || index_offset < HEADER_LEN || index_offset > end - TRAILER_LEN - 8
+        { return Err(HuffmanError::InvalidContainer("Invalid index position.")); }
+
+        reader.seek(SeekFrom::Start(index_offset))?;
+        let count: u64 = read_u64(reader)?;
+        if count != len.div_ceil(block_size as u64) || Some(end - TRAILER_LEN - 8 - index_offset) != count.checked_mul(8)
+        { return Err(HuffmanError::InvalidContainer("The index does not match the data length.")); }
+
+        let mut offsets: Vec<u64> = Vec::with_capacity(count as usize);
+        for _ in 0..count
+        { offsets.push(read_u64(reader)?); }
+        // Every block takes up at least one byte, which also rules out overlapping blocks.
+        let ordered: bool = offsets.windows(2).all(|w| w[0] < w[1]);
+        if !ordered || offsets.first().is_some_and(|x| *x != HEADER_LEN) || offsets.last().is_some_and(|x| *x >= index_offset)
+        { return Err(HuffmanError::InvalidContainer("Invalid block offsets.")); }
+
+        Ok(BlockIndex { block_size, len, offsets, index_offset })
+    }
+    /// Get the number of blocks.
+    #[must_use]
+    pub fn block_count(&self) -> usize {
+        self.offsets.len()
+    }
+    /// Get the length of the uncompressed data.
+    #[must_use]
+    pub const fn len(&self) -> u64 {
+        self.len
+    }
+    /// Checks whether the uncompressed data is empty.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Get the number of bytes per block.
+    #[must_use]
+    pub const fn block_size(&self) -> usize {
+        self.block_size
+    }
+    /// Get the block which contains a position of the uncompressed data.
+    /// # Arguments
+    /// * `position` - The position in the uncompressed data.
+    /// # Returns
+    /// `Some(usize)` with the number of the block, `None` if `position` is out of range.
+    #[must_use]
+    pub fn block_of(&self, position: u64) -> Option<usize> {
+        if position < self.len
+        { return Some((position / self.block_size as u64) as usize); }
+        None
+    }
+    /// Get the range of the uncompressed data a block contains.
+    /// # Arguments
+    /// * `block` - The number of the block.
+    /// # Returns
+    /// A `Range<u64>`.
+    /// # Panics
+    /// Panics if `block` is out of range.
+    #[must_use]
+    pub fn block_range(&self, block: usize) -> Range<u64> {
+        if block >= self.block_count()
+        { panic!("The block number is out of range."); }
+        let start: u64 = block as u64 * self.block_size as u64;
+        start..(start + self.block_size as u64).min(self.len)
+    }
+
+    /// The range of the compressed stream a block occupies.
+    fn compressed_range(&self, block: usize) -> Range<u64> {
+        self.offsets[block]..self.offsets.get(block + 1).copied().unwrap_or(self.index_offset)
+    }
+}
+
+/// Compresses bytes on multiple threads.
+/// # Arguments
+/// * `data` - The data to be compressed.
+/// * `config` - The block size and number of threads.
+/// # Returns
+/// A `Vec<u8>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::parallel::{compress, decompress, ParallelConfig};
+/// let data = "Lorem ipsum dolor sit amet, consectetur adipiscing elit.".repeat(1000);
+/// let enc  = compress(data.as_bytes(), &ParallelConfig::new(8192, 4));
+///
+/// assert!(enc.len() < data.len() * 3 / 4);
+/// assert_eq!(decompress(&enc, 4).unwrap(), data.as_bytes());
+/// ```
+#[must_use]
+pub fn compress(data: &[u8], config: &ParallelConfig) -> Vec<u8> {
+    let mut res: Vec<u8> = Vec::with_capacity(data.len() / 2);
+    // Neither reading a slice nor writing to a Vec can fail, and blocks are never empty.
+    compress_to(data, &mut res, config).unwrap();
+    res
+}
+
+/// Compresses everything from a reader on multiple threads into a writer.
+/// Batches of one block per thread are read, compressed in parallel and written in order.
+/// # Arguments
+/// * `reader` - The source of the data.
+/// * `writer` - The destination of the compressed stream.
+/// * `config` - The block size and number of threads.
+/// # Returns
+/// A `Result<u64, HuffmanError>` with the number of bytes read.
+pub fn compress_to<R: Read, W: Write>(mut reader: R, mut writer: W, config: &ParallelConfig) -> Result<u64, HuffmanError> {
+    let threads:     usize    = config.threads();
+    let mut offsets: Vec<u64> = Vec::new();
+    let mut offset:  u64      = HEADER_LEN;
+    let mut len:     u64      = 0;
+
+    writer.write_all(&PARALLEL_MAGIC)?;
+    writer.write_all(&[PARALLEL_VERSION])?;
+    writer.write_all(&(config.block_size as u32).to_le_bytes())?;
+
+    let mut done: bool = false;
+    while !done {
+        let mut batch: Vec<Vec<u8>> = Vec::with_capacity(threads);
+        while batch.len() < threads && !done {
+            let mut block: Vec<u8> = Vec::with_capacity(config.block_size);
+            (&mut reader).take(config.block_size as u64).read_to_end(&mut block)?;
+            done = block.len() < config.block_size;
+            if !block.is_empty()
+            { batch.push(block); }
+        }
+
+        for (block, enc) in batch.iter().zip(parallel_map(&batch, threads, |b| encode_block(b))) {
+            let enc: Vec<u8> = enc?;
+            writer.write_all(&enc)?;
+            offsets.push(offset);
+            offset += enc.len() as u64;
+            len    += block.len() as u64;
+        }
+    }
+
+    writer.write_all(&(offsets.len() as u64).to_le_bytes())?;
+    for o in &offsets
+    { writer.write_all(&o.to_le_bytes())?; }
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(&offset.to_le_bytes())?;
+    writer.write_all(&PARALLEL_MAGIC)?;
+    writer.flush()?;
+    Ok(len)
+}
+
+/// Decompresses data compressed by `compress` on multiple threads.
+/// # Arguments
+/// * `data` - The compressed data.
+/// * `threads` - The number of threads. `0` uses all available cores.
+/// # Returns
+/// A `Result<Vec<u8>, HuffmanError>`.
+pub fn decompress(data: &[u8], threads: usize) -> Result<Vec<u8>, HuffmanError> {
+    let mut res: Vec<u8> = Vec::with_capacity(data.len() * 2);
+    decompress_to(&mut Cursor::new(data), &mut res, threads)?;
+    Ok(res)
+}
+
+/// Decompresses a parallel Huffman stream on multiple threads into a writer.
+/// Batches of one block per thread are read, decompressed in parallel and written in order.
+/// # Arguments
+/// * `reader` - The compressed stream. It has to span the whole reader.
+/// * `writer` - The destination of the data.
+/// * `threads` - The number of threads. `0` uses all available cores.
+/// # Returns
+/// A `Result<u64, HuffmanError>` with the number of bytes written.
+pub fn decompress_to<R: Read + Seek, W: Write>(reader: &mut R, mut writer: W, threads: usize) -> Result<u64, HuffmanError> {
+    let index:   BlockIndex = BlockIndex::read_from(reader)?;
+    let threads: usize      = resolve_threads(threads);
+    let blocks:  Vec<usize> = (0..index.block_count()).collect();
+
+    for batch in blocks.chunks(threads) {
+        let start:   u64     = index.compressed_range(batch[0]).start;
+        let mut buf: Vec<u8> = vec![0; (index.compressed_range(batch[batch.len() - 1]).end - start) as usize];
+        reader.seek(SeekFrom::Start(start))?;
+        reader.read_exact(&mut buf)?;
+
+        let decoded = parallel_map(batch, threads, |b| {
+            let range: Range<u64> = index.compressed_range(*b);
+            check_block(&index, *b, decode_block(&mut &buf[(range.start - start) as usize..(range.end - start) as usize])?)
+        });
+        for block in decoded
+        { writer.write_all(&block?)?; }
+    }
+    writer.flush()?;
+    Ok(index.len())
+}
+
+/// Reads and decompresses a single block.
+/// # Arguments
+/// * `reader` - The compressed stream. It has to span the whole reader.
+/// * `index` - The index of the stream.
+/// * `block` - The number of the block.
+/// # Returns
+/// A `Result<Vec<u8>, HuffmanError>` with the bytes in `index.block_range(block)`.
+/// # Panics
+/// Panics if `block` is out of range.
+pub fn decompress_block<R: Read + Seek>(reader: &mut R, index: &BlockIndex, block: usize) -> Result<Vec<u8>, HuffmanError> {
+    if block >= index.block_count()
+    { panic!("The block number is out of range."); }
+
+    let range:   Range<u64> = index.compressed_range(block);
+    let mut buf: Vec<u8>    = vec![0; (range.end - range.start) as usize];
+    reader.seek(SeekFrom::Start(range.start))?;
+    reader.read_exact(&mut buf)?;
+    check_block(index, block, decode_block(&mut buf.as_slice())?)
+}
+
+fn check_block(index: &BlockIndex, block: usize, decoded: Option<Vec<u8>>) -> Result<Vec<u8>, HuffmanError> {
+    let range: Range<u64> = index.block_range(block);
+    match decoded {
+        Some(b) if b.len() as u64 == range.end - range.start => Ok(b),
+        _ => Err(HuffmanError::InvalidContainer("Block length does not match the index.")),
+    }
+}
+
+fn resolve_threads(threads: usize) -> usize {
+    match threads {
+        0 => thread::available_parallelism().map_or(1, |x| x.get()),
+        n => n,
+    }
+}
+
+/// Applies `f` to every item on up to `threads` threads, keeping the order.
+fn parallel_map<T, U, F>(items: &[T], threads: usize, f: F) -> Vec<U>
+where T: Sync, U: Send, F: Fn(&T) -> U + Sync {
+    if items.len() <= 1 || threads <= 1
+    { return items.iter().map(f).collect(); }
+
+    let f:     &F    = &f;
+    let chunk: usize = items.len().div_ceil(threads);
+    thread::scope(|s| {
+        let handles: Vec<thread::ScopedJoinHandle<Vec<U>>> = items.chunks(chunk)
+                                                                  .map(|c| s.spawn(move || c.iter().map(f).collect()))
+                                                                  .collect();
+        handles.into_iter().flat_map(|h| h.join().unwrap()).collect()
+    })
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes: [u8; 8] = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
\ No newline at end of file
@@ -0,0 +1,298 @@
+//! Streaming Huffman compression of bytes with bounded memory.
+//! The input is split into blocks of a fixed size, each of which gets its own length-limited canonical code.
+//! # Format
+//! All integers are little endian.
+//! * `[u8; 4]` - The magic number `LRHS`.
+//! * `u8` - The format version.
+//! * Any number of blocks:
+//!     * `u32` - The number of bytes in the block. `0` marks the end of the stream.
+//!     * `u16` - The number of symbols in the code table.
+//!     * `(u8, u8)` per symbol - The byte and its code length.
+//!     * `u32` - The exact number of payload bits.
+//!     * `[u8]` - The payload, padded to a full byte.
+//!     * `u32` - A CRC-32 of the uncompressed block.
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use bit_vec::BitVec;
+
+use super::{HuffmanError, MAX_CODE_LENGTH, get_frequency_of, limited_code_lengths, assign_canonical_codes, encode_symbols};
+use crate::compsci::checksum::crc::crc32;
+use super::table::DecodeTable;
+
+/// The magic number every LibRapid Huffman stream starts with.
+pub const STREAM_MAGIC: [u8; 4] = *b"LRHS";
+/// The version of the stream format written by `HuffmanWriter`.
+pub const STREAM_VERSION: u8 = 1;
+/// The block size used by `HuffmanWriter::new`.
+pub const DEFAULT_BLOCK_SIZE: usize = 1 << 16;
+/// The largest block size, so that the payload of a block with codes of `MAX_CODE_LENGTH` bits never has more bits than a `u32` can count.
+pub const MAX_BLOCK_SIZE: usize = u32::MAX as usize / MAX_CODE_LENGTH as usize;
+
+/// Compresses everything written to it block by block into an underlying `Write`.
+/// The stream is completed by `finish` or, ignoring errors, when the writer is dropped.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::stream::{HuffmanWriter, HuffmanReader};
+/// use std::io::{Read, Write};
+///
+/// let mut writer = HuffmanWriter::with_block_size(Vec::new(), 8);
+/// writer.write_all(b"Lorem ipsum dolor sit amet.").unwrap();
+/// let compressed: Vec<u8> = writer.finish().unwrap();
+///
+/// let mut res = String::new();
+/// HuffmanReader::new(compressed.as_slice()).read_to_string(&mut res).unwrap();
+/// assert_eq!(res, "Lorem ipsum dolor sit amet.");
+/// ```
+pub struct HuffmanWriter<W: Write> {
+    inner:          Option<W>,
+    block:          Vec<u8>,
+    block_size:     usize,
+    header_written: bool,
+}
+
+impl<W: Write> HuffmanWriter<W> {
+    /// Creates a new `HuffmanWriter` with blocks of `DEFAULT_BLOCK_SIZE` bytes.
+    /// # Arguments
+    /// * `inner` - The destination of the compressed stream.
+    /// # Returns
+    /// A new `HuffmanWriter`.
+    #[must_use]
+    pub fn new(inner: W) -> HuffmanWriter<W> {
+        HuffmanWriter::with_block_size(inner, DEFAULT_BLOCK_SIZE)
+    }
+    /// Creates a new `HuffmanWriter` with a custom block size.
+    /// Bigger blocks compress better, smaller blocks need less memory.
+    /// # Arguments
+    /// * `inner` - The destination of the compressed stream.
+    /// * `block_size` - The number of bytes per block.
+    /// # Returns
+    /// A new `HuffmanWriter`.
+    /// # Panics
+    /// Panics if `block_size` is `0` or greater than `MAX_BLOCK_SIZE`.
+    #[must_use]
+    pub fn with_block_size(inner: W, block_size: usize) -> HuffmanWriter<W> {
+        if block_size == 0 || block_size > MAX_BLOCK_SIZE
+        { panic!("The block size must be in the range of 1 to MAX_BLOCK_SIZE."); }
+
+        HuffmanWriter { inner:          Some(inner),
+                        block:          Vec::with_capacity(block_size),
+                        block_size,
+                        header_written: false }
+    }
+    /// Compresses the remaining data, ends the stream and returns the underlying writer.
+    /// # Returns
+    /// A `std::io::Result<W>`.
+    pub fn finish(mut self) -> io::Result<W> {
+        let res: io::Result<()> = self.write_end();
+        let inner: W            = self.inner.take().unwrap();
+        res.map(|_| inner)
+    }
+
+    fn write_end(&mut self) -> io::Result<()> {
+        self.write_block()?;
+        let inner: &mut W = self.inner.as_mut().unwrap();
+        inner.write_all(&0u32.to_le_bytes())?;
+        inner.flush()
+    }
+
+    fn write_block(&mut self) -> io::Result<()> {
+        let inner: &mut W = self.inner.as_mut().unwrap();
+        if !self.header_written {
+            inner.write_all(&STREAM_MAGIC)?;
+            inner.write_all(&[STREAM_VERSION])?;
+            self.header_written = true;
+        }
+        if self.block.is_empty()
+        { return Ok(()); }
+
+        inner.write_all(&encode_block(&self.block)?)?;
+        self.block.clear();
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for HuffmanWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n: usize = buf.len().min(self.block_size - self.block.len());
+        self.block.extend_from_slice(&buf[..n]);
+        if self.block.len() == self.block_size
+        { self.write_block()?; }
+        Ok(n)
+    }
+    /// Compresses the buffered data as a (possibly short) block and flushes the underlying writer.
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_block()?;
+        self.inner.as_mut().unwrap().flush()
+    }
+}
+
+impl<W: Write> Drop for HuffmanWriter<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some()
+        { let _ = self.write_end(); }
+    }
+}
+
+/// Decompresses a stream written by `HuffmanWriter` from an underlying `Read`, one block at a time.
+/// Never reads past the end of the stream.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::stream::{HuffmanWriter, HuffmanReader};
+/// use std::io::{Read, Write};
+///
+/// let data: Vec<u8> = (0..100_000u32).map(|x| (x % 7) as u8).collect();
+/// let mut writer = HuffmanWriter::new(Vec::new());
+/// writer.write_all(&data).unwrap();
+/// let mut compressed: Vec<u8> = writer.finish().unwrap();
+/// assert!(compressed.len() < data.len() / 2);
+///
+/// compressed[30] ^= 0x10; // Corrupt the payload.
+/// let mut res: Vec<u8> = Vec::new();
+/// assert!(HuffmanReader::new(compressed.as_slice()).read_to_end(&mut res).is_err());
+///
+/// // Reads after an error fail as well instead of returning old or later blocks.
+/// let mut writer = HuffmanWriter::with_block_size(Vec::new(), 4);
+/// writer.write_all(b"AAAABBBBCCCC").unwrap();
+/// let mut compressed: Vec<u8> = writer.finish().unwrap();
+/// compressed[5 + 2 * 17 - 1] ^= 0xFF; // Corrupt the checksum of the second block, each block takes 17 bytes.
+/// let mut reader = HuffmanReader::new(compressed.as_slice());
+/// let mut buf: [u8; 4] = [0; 4];
+/// reader.read_exact(&mut buf).unwrap();
+/// assert_eq!(&buf, b"AAAA");
+/// for _ in 0..3 {
+///     assert!(reader.read(&mut buf).is_err());
+/// }
+///
+/// // A truncated stream whose header claims 512 MiB of payload fails without allocating them.
+/// let forged: Vec<u8> = [&b"LRHS\x01"[..], &[0xFF; 4], &[0; 2], &[0xFF; 4]].concat();
+/// assert!(HuffmanReader::new(forged.as_slice()).read_to_end(&mut res).is_err());
+/// ```
+pub struct HuffmanReader<R: Read> {
+    inner:       R,
+    block:       Vec<u8>,
+    pos:         usize,
+    header_read: bool,
+    finished:    bool,
+    failed:      bool,
+}
+
+impl<R: Read> HuffmanReader<R> {
+    /// Creates a new `HuffmanReader`.
+    /// # Arguments
+    /// * `inner` - The source of the compressed stream.
+    /// # Returns
+    /// A new `HuffmanReader`.
+    #[must_use]
+    pub fn new(inner: R) -> HuffmanReader<R> {
+        HuffmanReader { inner,
+                        block:       Vec::new(),
+                        pos:         0,
+                        header_read: false,
+                        finished:    false,
+                        failed:      false }
+    }
+    /// Returns the underlying reader.
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn read_block(&mut self) -> Result<(), HuffmanError> {
+        // Nothing after a failed block can be trusted, so the reader stays failed instead of returning the old block again.
+        let res: Result<Option<Vec<u8>>, HuffmanError> = self.next_block();
+        self.pos = 0;
+        match res {
+            Ok(Some(block)) => { self.block = block; }
+            Ok(None)        => { self.block.clear(); self.finished = true; }
+            Err(e)          => { self.block.clear(); self.failed = true; return Err(e); }
+        }
+        Ok(())
+    }
+
+    fn next_block(&mut self) -> Result<Option<Vec<u8>>, HuffmanError> {
+        if !self.header_read {
+            let mut header: [u8; 5] = [0; 5];
+            self.inner.read_exact(&mut header)?;
+            if header[0..4] != STREAM_MAGIC
+            { return Err(HuffmanError::InvalidContainer("Not a LibRapid Huffman stream.")); }
+            if header[4] != STREAM_VERSION
+            { return Err(HuffmanError::InvalidContainer("Unsupported stream version.")); }
+            self.header_read = true;
+        }
+        decode_block(&mut self.inner)
+    }
+}
+
+impl<R: Read> Read for HuffmanReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.block.len() {
+            if self.failed
+            { return Err(HuffmanError::InvalidContainer("The stream failed to decode earlier.").into()); }
+            if self.finished
+            { return Ok(0); }
+            self.read_block()?;
+        }
+        let n: usize = buf.len().min(self.block.len() - self.pos);
+        buf[..n].copy_from_slice(&self.block[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+/// Encodes a non-empty block including its length, code table and checksum.
+pub(super) fn encode_block(block: &[u8]) -> Result<Vec<u8>, HuffmanError> {
+    let mut lengths: Vec<(u8, u8)>       = limited_code_lengths(&get_frequency_of(block), MAX_CODE_LENGTH)?;
+    let codes:       HashMap<u8, BitVec> = assign_canonical_codes(&mut lengths)?;
+    let payload:     BitVec              = encode_symbols(block, &codes)?;
+
+    let mut out: Vec<u8> = Vec::with_capacity(14 + lengths.len() * 2 + payload.len() / 8 + 1);
+    out.extend_from_slice(&(block.len() as u32).to_le_bytes());
+    out.extend_from_slice(&(lengths.len() as u16).to_le_bytes());
+    for (s, len) in &lengths {
+        out.push(*s);
+        out.push(*len);
+    }
+    out.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    out.extend_from_slice(&payload.to_bytes());
+    out.extend_from_slice(&crc32(block).to_le_bytes());
+    Ok(out)
+}
+
+/// Decodes the next block. Returns `None` for the end marker.
+pub(super) fn decode_block<R: Read>(reader: &mut R) -> Result<Option<Vec<u8>>, HuffmanError> {
+    let len: usize = read_u32(reader)? as usize;
+    if len == 0
+    { return Ok(None); }
+
+    let mut count: [u8; 2] = [0; 2];
+    reader.read_exact(&mut count)?;
+    let mut table: Vec<u8> = vec![0; u16::from_le_bytes(count) as usize * 2];
+    reader.read_exact(&mut table)?;
+    let lengths: Vec<(u8, u8)> = table.chunks(2).map(|x| (x[0], x[1])).collect();
+
+    let bits:        u64     = read_u32(reader)? as u64;
+    if bits > len as u64 * MAX_CODE_LENGTH as u64
+    { return Err(HuffmanError::InvalidContainer("Payload is longer than the block allows.")); }
+    // The header is not trusted yet, so the payload only grows as far as the input actually goes.
+    let mut payload: Vec<u8> = Vec::new();
+    reader.take(bits.div_ceil(8)).read_to_end(&mut payload)?;
+    if payload.len() as u64 != bits.div_ceil(8)
+    { return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()); }
+    let checksum:    u32     = read_u32(reader)?;
+
+    let mut bitvec: BitVec  = BitVec::from_bytes(&payload);
+    bitvec.truncate(bits as usize);
+    let block:      Vec<u8> = DecodeTable::from_lengths(&lengths)?.decode(&bitvec)?;
+
+    if block.len() != len
+    { return Err(HuffmanError::InvalidContainer("Block length does not match its contents.")); }
+    if crc32(&block) != checksum
+    { return Err(HuffmanError::ChecksumMismatch); }
+    Ok(Some(block))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut bytes: [u8; 4] = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
\ No newline at end of file
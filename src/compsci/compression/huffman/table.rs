@@ -0,0 +1,234 @@
+//! A table-driven Huffman decoder, which resolves several bits per lookup instead of walking the tree bit by bit.
+use std::collections::HashMap;
+use std::hash::Hash;
+use bit_vec::BitVec;
+
+use super::{Node, HuffmanError, assign_codes, assign_canonical_codes};
+
+/// The number of bits the first table resolves. Longer codes continue in sub-tables.
+const PRIMARY_BITS: u8 = 10;
+
+#[derive(Clone, Copy)]
+enum Entry<T> {
+    /// No code starts with these bits.
+    Empty,
+    /// A symbol and the number of bits of this table its code uses.
+    Leaf(T, u8),
+    /// The index of the table the code continues in.
+    Sub(usize),
+}
+
+struct Table<T> {
+    bits:    u8,
+    entries: Vec<Entry<T>>,
+}
+
+/// A lookup-table decoder for Huffman codes.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::huffman::{Encode, table::DecodeTable};
+/// let (enc, root) = "Lorem Ipsum".full_encode();
+/// let table = DecodeTable::from_tree(&root).unwrap();
+///
+/// assert_eq!(table.decode_string(&enc).unwrap(), "Lorem Ipsum");
+/// ```
+pub struct DecodeTable<T = char> {
+    tables: Vec<Table<T>>,
+}
+
+impl<T: Hash + Ord + Copy> DecodeTable<T> {
+    /// Creates a new `DecodeTable` out of the code lengths of canonical codes.
+    /// # Arguments
+    /// * `lengths` - The code length of each symbol.
+    /// # Returns
+    /// A `Result<DecodeTable<T>, HuffmanError>`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::compression::huffman::{canonical_encode, table::DecodeTable};
+    /// let (enc, lengths) = canonical_encode("Lorem Ipsum").unwrap();
+    /// let table = DecodeTable::from_lengths(&lengths).unwrap();
+    ///
+    /// assert_eq!(table.decode_string(&enc).unwrap(), "Lorem Ipsum");
+    /// ```
+    pub fn from_lengths(lengths: &[(T, u8)]) -> Result<DecodeTable<T>, HuffmanError> {
+        DecodeTable::from_codes(&assign_canonical_codes(&mut lengths.to_vec())?)
+    }
+    /// Creates a new `DecodeTable` out of any Huffman tree.
+    /// # Arguments
+    /// * `root` - The Huffman tree.
+    /// # Returns
+    /// A `Result<DecodeTable<T>, HuffmanError>`.
+    pub fn from_tree(root: &Node<T>) -> Result<DecodeTable<T>, HuffmanError> {
+        let mut codes: HashMap<T, BitVec> = HashMap::new();
+        assign_codes(root, &mut codes, &mut BitVec::new());
+        DecodeTable::from_codes(&codes)
+    }
+
+    fn from_codes(codes: &HashMap<T, BitVec>) -> Result<DecodeTable<T>, HuffmanError> {
+        let mut flat: Vec<(u64, u8, T)> = Vec::with_capacity(codes.len());
+        for (s, code) in codes {
+            if code.is_empty()
+            { return Err(HuffmanError::CorruptTree); }
+            if code.len() > 64
+            { return Err(HuffmanError::CodeTooLong); }
+            let value: u64 = code.iter().fold(0, |acc, b| (acc << 1) | b as u64);
+            flat.push((value, code.len() as u8, *s));
+        }
+
+        let mut res: DecodeTable<T> = DecodeTable { tables: Vec::new() };
+        res.build(&flat)?;
+        Ok(res)
+    }
+
+    /// Builds the table for `codes` (relative to this level) and returns its index.
+    fn build(&mut self, codes: &[(u64, u8, T)]) -> Result<usize, HuffmanError> {
+        let max_len: u8    = codes.iter().map(|x| x.1).max().unwrap_or(1);
+        let bits:    u8    = max_len.min(PRIMARY_BITS);
+        let index:   usize = self.tables.len();
+        self.tables.push(Table { bits, entries: vec![Entry::Empty; 1 << bits] });
+
+        let mut longer: HashMap<usize, Vec<(u64, u8, T)>> = HashMap::new();
+        for (value, len, s) in codes {
+            if *len <= bits {
+                let start: usize = (*value as usize) << (bits - len);
+                for entry in &mut self.tables[index].entries[start..start + (1 << (bits - len))] {
+                    if !matches!(entry, Entry::Empty)
+                    { return Err(HuffmanError::CorruptTree); }
+                    *entry = Entry::Leaf(*s, *len);
+                }
+            }
+            else {
+                let rest: u8 = len - bits;
+                longer.entry((value >> rest) as usize)
+                      .or_default()
+                      .push((value & ((1 << rest) - 1), rest, *s));
+            }
+        }
+
+        for (prefix, sub_codes) in longer {
+            if !matches!(self.tables[index].entries[prefix], Entry::Empty)
+            { return Err(HuffmanError::CorruptTree); }
+            let sub: usize = self.build(&sub_codes)?;
+            self.tables[index].entries[prefix] = Entry::Sub(sub);
+        }
+        Ok(index)
+    }
+    /// Decodes a Huffman encoded BitVec.
+    /// # Arguments
+    /// * `bitvec` - The Huffman encoded message.
+    /// # Returns
+    /// A `Result<Vec<T>, HuffmanError>`, failing in the same cases as `try_huffman_decode`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::compression::huffman::{Encode, table::DecodeTable};
+    /// let data: Vec<u8> = vec![1, 2, 3, 3, 3, 3, 200];
+    /// let (enc, root) = data.full_encode();
+    ///
+    /// assert_eq!(DecodeTable::from_tree(&root).unwrap().decode(&enc).unwrap(), data);
+    /// ```
+    pub fn decode(&self, bitvec: &BitVec) -> Result<Vec<T>, HuffmanError> {
+        let mut res: Vec<T> = Vec::with_capacity(bitvec.len() / 4);
+        self.decode_with(bitvec, |s| res.push(s))?;
+        Ok(res)
+    }
+
+    fn decode_with<F: FnMut(T)>(&self, bitvec: &BitVec, mut f: F) -> Result<(), HuffmanError> {
+        let mut reader: BitReader = BitReader::new(bitvec);
+
+        while reader.remaining() > 0 {
+            f(self.decode_next(&mut reader)?);
+        }
+        Ok(())
+    }
+    /// Decodes the next symbol out of any source of bits.
+    pub(in crate::compsci::compression) fn decode_next<B: BitSource>(&self, reader: &mut B) -> Result<T, HuffmanError> {
+        let mut table: &Table<T> = &self.tables[0];
+        loop {
+            match table.entries[reader.peek(table.bits) as usize] {
+                Entry::Leaf(s, len) => {
+                    if (len as usize) > reader.remaining()
+                    { return Err(HuffmanError::TruncatedBitstream); }
+                    reader.consume(len);
+                    return Ok(s);
+                }
+                Entry::Sub(i) => {
+                    if (table.bits as usize) >= reader.remaining()
+                    { return Err(HuffmanError::TruncatedBitstream); }
+                    reader.consume(table.bits);
+                    table = &self.tables[i];
+                }
+                Entry::Empty => {
+                    if (table.bits as usize) > reader.remaining()
+                    { return Err(HuffmanError::TruncatedBitstream); }
+                    return Err(HuffmanError::CorruptTree);
+                }
+            }
+        }
+    }
+}
+
+impl DecodeTable<char> {
+    /// Decodes a Huffman encoded BitVec into a `String`.
+    /// # Arguments
+    /// * `bitvec` - The Huffman encoded message.
+    /// # Returns
+    /// A `Result<String, HuffmanError>`.
+    pub fn decode_string(&self, bitvec: &BitVec) -> Result<String, HuffmanError> {
+        let mut res: String = String::with_capacity(bitvec.len() / 4);
+        self.decode_with(bitvec, |c| res.push(c))?;
+        Ok(res)
+    }
+}
+
+/// A source of bits for `DecodeTable`, which sees each code most significant bit first.
+pub(in crate::compsci::compression) trait BitSource {
+    /// The number of bits that have not been consumed yet.
+    fn remaining(&self) -> usize;
+    /// Looks at the next `n` bits (`1..=32`) without consuming them, the first one being the most significant.
+    /// Missing bits at the end are `0`.
+    fn peek(&mut self, n: u8) -> u64;
+    /// Consumes `n` bits.
+    fn consume(&mut self, n: u8);
+}
+
+/// Reads the bits of a `BitVec` most significant first, straight from its `u32` blocks.
+struct BitReader<'a> {
+    blocks: &'a [u32],
+    next:   usize,
+    buffer: u64,
+    count:  u8,
+    left:   usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bitvec: &'a BitVec) -> BitReader<'a> {
+        BitReader { blocks: bitvec.storage(),
+                    next:   0,
+                    buffer: 0,
+                    count:  0,
+                    left:   bitvec.len() }
+    }
+}
+
+impl BitSource for BitReader<'_> {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.left
+    }
+    #[inline]
+    fn peek(&mut self, n: u8) -> u64 {
+        if self.count < n && self.next < self.blocks.len() {
+            // `BitVec` stores the first bit in the least significant bit of a block.
+            self.buffer |= (self.blocks[self.next].reverse_bits() as u64) << (32 - self.count);
+            self.count  += 32;
+            self.next   += 1;
+        }
+        self.buffer >> (64 - n)
+    }
+    #[inline]
+    fn consume(&mut self, n: u8) {
+        self.buffer <<= n;
+        self.count    = self.count.saturating_sub(n);
+        self.left    -= n as usize;
+    }
+}
\ No newline at end of file
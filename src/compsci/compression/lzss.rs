@@ -0,0 +1,382 @@
+//! Implements the LZSS-compression algorithm (Lempel-Ziv-Storer-Szymanski) for byte slices.
+//! Repeated sequences are replaced by references into a sliding window of the already processed data.
+//! # Format
+//! All integers are little endian.
+//! * `[u8; 4]` - The magic number `LRLZ`.
+//! * `u8` - The format version.
+//! * `u8` - The minimum match length.
+//! * `u64` - The length of the uncompressed data.
+//! * Groups of up to 8 tokens, each preceded by a flag byte. Bit `i` (least significant first) is set if token `i` is a match:
+//!     * Literal: `u8` - The byte itself.
+//!     * Match: `u16` - The distance back into the window, `u8` - The match length minus the minimum match length.
+use core::fmt;
+use std::io::{Read, Write};
+
+use crate::compsci::compression::huffman::{HuffmanError, stream::{HuffmanWriter, HuffmanReader}};
+
+/// The magic number every LibRapid LZSS stream starts with.
+pub const LZSS_MAGIC: [u8; 4] = *b"LRLZ";
+/// The version of the format written by `compress`.
+pub const LZSS_VERSION: u8 = 1;
+const HEADER_LEN: usize = 14;
+/// The number of hash chain entries looked at before settling for the best match so far.
+const MAX_CHAIN: usize = 256;
+const HASH_BITS: u32   = 15;
+const NONE:      usize = usize::MAX;
+
+/// The errors that can occur while decompressing LZSS data.
+#[derive(Debug)]
+pub enum LzssError {
+    /// The header is malformed. Contains the reason.
+    InvalidHeader(&'static str),
+    /// The data ended before the announced length was reached.
+    Truncated,
+    /// The match at the given offset of the compressed data points before the start of the output.
+    InvalidDistance(usize),
+    /// The decompressed data would be longer than announced in the header.
+    LengthMismatch,
+    /// The Huffman backend failed.
+    Huffman(HuffmanError),
+}
+
+impl fmt::Display for LzssError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LzssError::InvalidHeader(s)   => write!(f, "Invalid header: {}", s),
+            LzssError::Truncated          => write!(f, "The compressed data is truncated."),
+            LzssError::InvalidDistance(i) => write!(f, "The match at offset {} points before the start of the data.", i),
+            LzssError::LengthMismatch     => write!(f, "The data is longer than announced."),
+            LzssError::Huffman(e)         => write!(f, "Huffman error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for LzssError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LzssError::Huffman(e) => Some(e),
+            _                     => None,
+        }
+    }
+}
+
+impl From<HuffmanError> for LzssError {
+    fn from(e: HuffmanError) -> LzssError {
+        LzssError::Huffman(e)
+    }
+}
+
+/// The parameters of the LZSS compressor.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LzssConfig {
+    window_size: usize,
+    min_match:   usize,
+    max_match:   usize,
+}
+
+impl LzssConfig {
+    /// Create a new `LzssConfig`.
+    /// # Arguments
+    /// * `window_size` - How far back matches may reach, `1..=65535`.
+    /// * `min_match` - The shortest match worth referencing, `3..=255`.
+    /// * `max_match` - The longest match, at most `min_match + 255`.
+    /// # Returns
+    /// A new `LzssConfig`.
+    /// # Panics
+    /// Panics if one of the arguments is out of range.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::compression::lzss::{LzssConfig, compress, decompress};
+    /// let config = LzssConfig::new(1024, 4, 64);
+    /// let enc = compress(b"abcdabcdabcdabcd", &config);
+    ///
+    /// assert_eq!(decompress(&enc).unwrap(), b"abcdabcdabcdabcd");
+    /// ```
+    #[must_use]
+    pub const fn new(window_size: usize, min_match: usize, max_match: usize) -> LzssConfig {
+        if window_size == 0 || window_size > u16::MAX as usize
+        { panic!("The window size must be in the range of 1 to 65535."); }
+        if min_match < 3 || min_match > 255
+        { panic!("The minimum match length must be in the range of 3 to 255."); }
+        if max_match < min_match || max_match > min_match + 255
+        { panic!("The maximum match length must be in the range of min_match to min_match + 255."); }
+
+        LzssConfig { window_size, min_match, max_match }
+    }
+    /// Creates a new `LzssConfig` with a window of 4096 bytes and matches of 3 to 258 bytes.
+    #[must_use]
+    pub const fn new_default() -> LzssConfig {
+        LzssConfig::new(4096, 3, 258)
+    }
+    /// Get the `window_size` field.
+    #[must_use]
+    pub const fn window_size(&self) -> usize {
+        self.window_size
+    }
+    /// Get the `min_match` field.
+    #[must_use]
+    pub const fn min_match(&self) -> usize {
+        self.min_match
+    }
+    /// Get the `max_match` field.
+    #[must_use]
+    pub const fn max_match(&self) -> usize {
+        self.max_match
+    }
+}
+
+/// A single LZSS token.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Token {
+    /// A byte that is copied as it is.
+    Literal(u8),
+    /// A copy of `length` bytes starting `distance` bytes back in the output.
+    Match { distance: usize, length: usize },
+}
+
+/// Splits data into literals and matches.
+/// # Arguments
+/// * `data` - The data to be tokenized.
+/// * `config` - The parameters of the compressor.
+/// # Returns
+/// A `Vec<Token>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::lzss::{LzssConfig, Token, tokenize};
+/// let tokens = tokenize(b"abcabcabc", &LzssConfig::new_default());
+///
+/// assert_eq!(tokens, vec![Token::Literal(b'a'),
+///                         Token::Literal(b'b'),
+///                         Token::Literal(b'c'),
+///                         Token::Match { distance: 3, length: 6 }]);
+/// ```
+#[must_use]
+pub fn tokenize(data: &[u8], config: &LzssConfig) -> Vec<Token> {
+    let mut res:  Vec<Token> = Vec::with_capacity(data.len() / 2);
+    let mut head: Vec<usize> = vec![NONE; 1 << HASH_BITS];
+    let mut prev: Vec<usize> = vec![NONE; data.len()];
+    let mut i:    usize      = 0;
+
+    while i < data.len() {
+        let (mut best_len, mut best_dist) = (0, 0);
+
+        if i + config.min_match <= data.len() {
+            let max_len:   usize = config.max_match.min(data.len() - i);
+            let mut j:     usize = head[hash(&data[i..])];
+            let mut tries: usize = 0;
+
+            while j != NONE && i - j <= config.window_size && tries < MAX_CHAIN {
+                let len: usize = data[j..].iter()
+                                          .zip(&data[i..i + max_len])
+                                          .take_while(|(a, b)| a == b)
+                                          .count();
+                if len > best_len {
+                    best_len  = len;
+                    best_dist = i - j;
+                    if len == max_len
+                    { break; }
+                }
+                j = prev[j];
+                tries += 1;
+            }
+        }
+
+        let step: usize = if best_len >= config.min_match {
+            res.push(Token::Match { distance: best_dist, length: best_len });
+            best_len
+        }
+        else {
+            res.push(Token::Literal(data[i]));
+            1
+        };
+
+        for k in i..i + step {
+            if k + 3 <= data.len() {
+                let h: usize = hash(&data[k..]);
+                prev[k] = head[h];
+                head[h] = k;
+            }
+        }
+        i += step;
+    }
+    res
+}
+
+#[inline]
+fn hash(data: &[u8]) -> usize {
+    let v: u32 = (data[0] as u32) << 16 | (data[1] as u32) << 8 | data[2] as u32;
+    (v.wrapping_mul(2_654_435_761) >> (32 - HASH_BITS)) as usize
+}
+
+/// Rebuilds the data out of its tokens.
+/// # Arguments
+/// * `tokens` - The tokens.
+/// # Returns
+/// A `Result<Vec<u8>, LzssError>`. `LzssError::InvalidDistance` contains the index of the offending token.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::lzss::{Token, detokenize};
+/// let tokens = vec![Token::Literal(b'a'), Token::Match { distance: 1, length: 4 }];
+///
+/// assert_eq!(detokenize(&tokens).unwrap(), b"aaaaa");
+/// assert!(detokenize(&[Token::Match { distance: 1, length: 3 }]).is_err());
+/// ```
+pub fn detokenize(tokens: &[Token]) -> Result<Vec<u8>, LzssError> {
+    let mut res: Vec<u8> = Vec::with_capacity(tokens.len() * 2);
+    for (i, t) in tokens.iter().enumerate() {
+        match *t {
+            Token::Literal(b)                 => { res.push(b); }
+            Token::Match { distance, length } => {
+                if distance == 0 || distance > res.len()
+                { return Err(LzssError::InvalidDistance(i)); }
+                copy_match(&mut res, distance, length);
+            }
+        }
+    }
+    Ok(res)
+}
+
+/// Copies `length` bytes from `distance` bytes back, which may overlap with the bytes being written.
+#[inline]
+pub(super) fn copy_match(out: &mut Vec<u8>, distance: usize, length: usize) {
+    let start: usize = out.len() - distance;
+    if distance >= length
+    { out.extend_from_within(start..start + length); }
+    else {
+        for k in 0..length {
+            out.push(out[start + k]);
+        }
+    }
+}
+
+/// Compresses a byte slice with LZSS.
+/// # Arguments
+/// * `data` - The data to be compressed.
+/// * `config` - The parameters of the compressor.
+/// # Returns
+/// A `Vec<u8>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::lzss::{LzssConfig, compress, decompress};
+/// let data = "Lorem ipsum dolor sit amet, Lorem ipsum dolor sit amet.".repeat(10);
+/// let enc  = compress(data.as_bytes(), &LzssConfig::new_default());
+///
+/// assert!(enc.len() < data.len() / 5);
+/// assert_eq!(decompress(&enc).unwrap(), data.as_bytes());
+/// ```
+#[must_use]
+pub fn compress(data: &[u8], config: &LzssConfig) -> Vec<u8> {
+    let tokens:  Vec<Token> = tokenize(data, config);
+    let mut res: Vec<u8>    = Vec::with_capacity(HEADER_LEN + tokens.len() * 2);
+    res.extend_from_slice(&LZSS_MAGIC);
+    res.push(LZSS_VERSION);
+    res.push(config.min_match as u8);
+    res.extend_from_slice(&(data.len() as u64).to_le_bytes());
+
+    for group in tokens.chunks(8) {
+        let flag_pos: usize = res.len();
+        res.push(0);
+        for (bit, t) in group.iter().enumerate() {
+            match *t {
+                Token::Literal(b)                 => { res.push(b); }
+                Token::Match { distance, length } => {
+                    res[flag_pos] |= 1 << bit;
+                    res.extend_from_slice(&(distance as u16).to_le_bytes());
+                    res.push((length - config.min_match) as u8);
+                }
+            }
+        }
+    }
+    res
+}
+
+/// Decompresses data compressed by `compress`.
+/// # Arguments
+/// * `data` - The compressed data.
+/// # Returns
+/// A `Result<Vec<u8>, LzssError>`.
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>, LzssError> {
+    if data.len() < HEADER_LEN || data[0..4] != LZSS_MAGIC
+    { return Err(LzssError::InvalidHeader("Not a LibRapid LZSS stream.")); }
+    if data[4] != LZSS_VERSION
+    { return Err(LzssError::InvalidHeader("Unsupported version.")); }
+
+    let min_match: usize = data[5] as usize;
+    let mut len_bytes: [u8; 8] = [0; 8];
+    len_bytes.copy_from_slice(&data[6..HEADER_LEN]);
+    let len: usize = u64::from_le_bytes(len_bytes) as usize;
+
+    // Every compressed byte expands to at most `min_match + 255` bytes, which bounds the allocation.
+    let mut res: Vec<u8> = Vec::with_capacity(len.min(data.len().saturating_mul(min_match + 255)));
+    let mut pos: usize   = HEADER_LEN;
+
+    while res.len() < len {
+        let flags: u8 = *data.get(pos).ok_or(LzssError::Truncated)?;
+        pos += 1;
+        for bit in 0..8 {
+            if res.len() >= len
+            { break; }
+            if flags & (1 << bit) == 0 {
+                res.push(*data.get(pos).ok_or(LzssError::Truncated)?);
+                pos += 1;
+                continue;
+            }
+
+            let t: &[u8] = data.get(pos..pos + 3).ok_or(LzssError::Truncated)?;
+            let distance: usize = u16::from_le_bytes([t[0], t[1]]) as usize;
+            let length:   usize = t[2] as usize + min_match;
+            if distance == 0 || distance > res.len()
+            { return Err(LzssError::InvalidDistance(pos)); }
+            if length > len - res.len()
+            { return Err(LzssError::LengthMismatch); }
+            copy_match(&mut res, distance, length);
+            pos += 3;
+        }
+    }
+    Ok(res)
+}
+
+/// Compresses a byte slice with LZSS and then with the streaming Huffman coder, which removes the redundancy left in the tokens.
+/// # Arguments
+/// * `data` - The data to be compressed.
+/// * `config` - The parameters of the LZSS stage.
+/// # Returns
+/// A `Vec<u8>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::lzss::{LzssConfig, compress, compress_huffman, decompress_huffman};
+/// let data: String = (0..2000u32).map(|x| format!("{} ", x * x % 997)).collect();
+/// let enc  = compress_huffman(data.as_bytes(), &LzssConfig::new_default());
+///
+/// assert!(enc.len() < compress(data.as_bytes(), &LzssConfig::new_default()).len());
+/// assert_eq!(decompress_huffman(&enc).unwrap(), data.as_bytes());
+/// ```
+#[must_use]
+pub fn compress_huffman(data: &[u8], config: &LzssConfig) -> Vec<u8> {
+    let mut writer: HuffmanWriter<Vec<u8>> = HuffmanWriter::new(Vec::new());
+    // Writing into a `Vec` can't fail.
+    writer.write_all(&compress(data, config)).unwrap();
+    writer.finish().unwrap()
+}
+
+/// Decompresses data compressed by `compress_huffman`.
+/// # Arguments
+/// * `data` - The compressed data.
+/// # Returns
+/// A `Result<Vec<u8>, LzssError>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::lzss::{LzssConfig, LzssError, compress_huffman, decompress_huffman};
+/// use lib_rapid::compsci::compression::huffman::HuffmanError;
+/// let mut enc = compress_huffman(b"Lorem ipsum dolor sit amet.", &LzssConfig::new_default());
+/// let end: usize = enc.len() - 4;
+/// enc[end - 1] ^= 0x01; // Corrupt the checksum in front of the end marker.
+///
+/// assert!(matches!(decompress_huffman(&enc), Err(LzssError::Huffman(HuffmanError::ChecksumMismatch))));
+/// ```
+pub fn decompress_huffman(data: &[u8]) -> Result<Vec<u8>, LzssError> {
+    let mut lzss: Vec<u8> = Vec::new();
+    HuffmanReader::new(data).read_to_end(&mut lzss)
+                            .map_err(|e| LzssError::Huffman(e.into()))?;
+    decompress(&lzss)
+}
\ No newline at end of file
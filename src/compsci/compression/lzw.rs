@@ -0,0 +1,413 @@
+//! Implements the LZW-compression algorithm (Lempel-Ziv-Welch) with variable code widths.
+//! The dictionary grows with every code and is reset with a clear code once it is full.
+//! Two ways of packing the codes are supported:
+//! * GIF: codes of up to 12 bits, a clear code at the start and an end-of-information code at the end.
+//! * Unix `compress` (`.Z` files): codes of 9 up to 16 bits. Whenever the code width changes or the dictionary is cleared,
+//!   the output is padded to a whole group of 8 codes, like the original implementation does.
+//!
+//! Codes are collected in a `BitVec` in the order they are written, least significant bit first.
+//! `pack` and `unpack` convert between the `BitVec` and bytes.
+use core::fmt;
+use std::collections::HashMap;
+use bit_vec::BitVec;
+
+/// The magic number of `.Z` files.
+pub const Z_MAGIC: [u8; 2] = [0x1F, 0x9D];
+/// The flag of `.Z` files which enables the clear code.
+const BLOCK_MODE: u8 = 0x80;
+/// The largest code width of GIF.
+const GIF_MAX_BITS: u8 = 12;
+
+/// The errors that can occur while decompressing LZW data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LzwError {
+    /// The header is malformed. Contains the reason.
+    InvalidHeader(&'static str),
+    /// A code at the given bit position is neither a literal nor in the dictionary.
+    InvalidCode(usize),
+    /// The byte at the given index can't be coded with the literal width of the configuration.
+    InvalidSymbol(usize),
+    /// The data ended before the end-of-information code.
+    Truncated,
+}
+
+impl fmt::Display for LzwError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LzwError::InvalidHeader(s) => write!(f, "Invalid header: {}", s),
+            LzwError::InvalidCode(i)   => write!(f, "Invalid code at bit {}.", i),
+            LzwError::InvalidSymbol(i) => write!(f, "The byte at index {} is too big for the literal width.", i),
+            LzwError::Truncated        => write!(f, "The compressed data is truncated."),
+        }
+    }
+}
+
+impl std::error::Error for LzwError { }
+
+/// The code widths and the packing of an LZW stream.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LzwConfig {
+    literal_bits: u8,
+    max_bits:     u8,
+    clear:        bool,
+    gif:          bool,
+}
+
+impl LzwConfig {
+    /// Creates a new `LzwConfig` for GIF image data.
+    /// # Arguments
+    /// * `min_code_size` - The number of bits per pixel, `2..=8`. Codes start one bit wider.
+    /// # Returns
+    /// A new `LzwConfig`.
+    /// # Panics
+    /// Panics if `min_code_size` is out of range.
+    #[must_use]
+    pub const fn gif(min_code_size: u8) -> LzwConfig {
+        if min_code_size < 2 || min_code_size > 8
+        { panic!("The minimum code size must be in the range of 2 to 8."); }
+        LzwConfig { literal_bits: min_code_size, max_bits: GIF_MAX_BITS, clear: true, gif: true }
+    }
+    /// Creates a new `LzwConfig` for Unix `compress`.
+    /// # Arguments
+    /// * `max_bits` - The largest code width, `9..=16`.
+    /// # Returns
+    /// A new `LzwConfig`.
+    /// # Panics
+    /// Panics if `max_bits` is out of range.
+    #[must_use]
+    pub const fn unix(max_bits: u8) -> LzwConfig {
+        if max_bits < 9 || max_bits > 16
+        { panic!("The maximum code width must be in the range of 9 to 16."); }
+        LzwConfig { literal_bits: 8, max_bits, clear: true, gif: false }
+    }
+    /// Creates a new `LzwConfig` for Unix `compress` with codes of up to 16 bits, its default.
+    #[must_use]
+    pub const fn new_default() -> LzwConfig {
+        LzwConfig::unix(16)
+    }
+    /// Get the `max_bits` field.
+    #[must_use]
+    pub const fn max_bits(&self) -> u8 {
+        self.max_bits
+    }
+
+    /// The number of literal codes.
+    const fn literals(&self) -> usize {
+        1 << self.literal_bits
+    }
+    /// The first code that is free for the dictionary.
+    const fn first(&self) -> usize {
+        self.literals() + self.clear as usize + self.gif as usize
+    }
+}
+
+/// Keeps track of the code width the same way encoder and decoder do.
+struct Width {
+    config: LzwConfig,
+    bits:   u8,
+    /// The number of codes since the last clear code.
+    count:  usize,
+    /// Where the current group of 8 codes started, for the padding of `compress`.
+    origin: usize,
+}
+
+impl Width {
+    fn new(config: LzwConfig) -> Width {
+        Width { config, bits: config.literal_bits + 1, count: 0, origin: 0 }
+    }
+    /// Returns the width of the next code and the position it starts at.
+    /// The code before it may have been followed by an entry the decoder doesn't know yet, so widths grow one code late.
+    fn next(&mut self, pos: usize) -> (u8, usize) {
+        self.count += 1;
+        let mut pos: usize = pos;
+        while self.bits < self.config.max_bits && self.config.first() + self.count >= (1 << self.bits) + 2 {
+            pos = self.pad(pos);
+            self.bits += 1;
+        }
+        (self.bits, pos)
+    }
+    /// Starts over after a clear code at `pos`. Returns the position of the next code.
+    fn reset(&mut self, pos: usize) -> usize {
+        let pos: usize = self.pad(pos);
+        self.bits  = self.config.literal_bits + 1;
+        self.count = 0;
+        pos
+    }
+    /// `compress` writes its codes in groups of 8 and fills the current group when the width changes.
+    fn pad(&mut self, pos: usize) -> usize {
+        if self.config.gif
+        { return pos; }
+        let group: usize = self.bits as usize * 8;
+        self.origin += (pos - self.origin).div_ceil(group) * group;
+        self.origin
+    }
+}
+
+/// Compresses data into LZW codes.
+/// # Arguments
+/// * `data` - The data to be compressed.
+/// * `config` - The code widths and packing.
+/// # Returns
+/// A `Result<BitVec, LzwError>` with the codes, least significant bit first.
+/// Fails with `LzwError::InvalidSymbol` if a byte doesn't fit into the literal width of a GIF configuration.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::lzw::{LzwConfig, encode, decode};
+/// let data = b"TOBEORNOTTOBEORTOBEORNOT";
+/// let enc  = encode(data, &LzwConfig::new_default()).unwrap();
+///
+/// assert_eq!(enc.len(), 16 * 9);
+/// assert_eq!(decode(&enc, &LzwConfig::new_default()).unwrap(), data);
+/// ```
+pub fn encode(data: &[u8], config: &LzwConfig) -> Result<BitVec, LzwError> {
+    let mut res:   BitVec                  = BitVec::with_capacity(data.len() * 8);
+    let mut width: Width                   = Width::new(*config);
+    let mut dict:  HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next:  usize                   = config.first();
+    let mut w:     Option<u16>             = None;
+    let capacity:  usize                   = 1 << config.max_bits;
+
+    if config.gif
+    { emit(&mut res, &mut width, config.literals()); reset(&mut res, &mut width); }
+
+    for (i, c) in data.iter().enumerate() {
+        if *c as usize >= config.literals()
+        { return Err(LzwError::InvalidSymbol(i)); }
+        let prefix: u16 = match w {
+            None    => { w = Some(*c as u16); continue; }
+            Some(p) => p,
+        };
+        if let Some(code) = dict.get(&(prefix, *c)) {
+            w = Some(*code);
+            continue;
+        }
+
+        emit(&mut res, &mut width, prefix as usize);
+        if next < capacity {
+            dict.insert((prefix, *c), next as u16);
+            next += 1;
+        }
+        if next == capacity && config.clear {
+            emit(&mut res, &mut width, config.literals());
+            reset(&mut res, &mut width);
+            dict.clear();
+            next = config.first();
+        }
+        w = Some(*c as u16);
+    }
+
+    if let Some(p) = w
+    { emit(&mut res, &mut width, p as usize); }
+    if config.gif
+    { emit(&mut res, &mut width, config.literals() + 1); }
+    Ok(res)
+}
+
+fn emit(bits: &mut BitVec, width: &mut Width, code: usize) {
+    let (n, pos) = width.next(bits.len());
+    bits.grow(pos - bits.len(), false);
+    for i in 0..n
+    { bits.push((code >> i) & 1 == 1); }
+}
+
+fn reset(bits: &mut BitVec, width: &mut Width) {
+    let pos: usize = width.reset(bits.len());
+    bits.grow(pos - bits.len(), false);
+}
+
+/// Decompresses LZW codes.
+/// # Arguments
+/// * `bits` - The codes, least significant bit first.
+/// * `config` - The code widths and packing the data was compressed with.
+/// # Returns
+/// A `Result<Vec<u8>, LzwError>`. GIF data has to end with the end-of-information code,
+/// data of `compress` ends when there are no bits left for another code.
+pub fn decode(bits: &BitVec, config: &LzwConfig) -> Result<Vec<u8>, LzwError> {
+    let mut res:   Vec<u8>        = Vec::with_capacity(bits.len() / 4);
+    let mut width: Width          = Width::new(*config);
+    // Every dictionary entry is an earlier code plus one byte.
+    let mut dict:  Vec<(u16, u8)> = vec![(0, 0); 1 << config.max_bits];
+    let mut next:  usize          = config.first();
+    let mut prev:  Option<usize>  = None;
+    let mut pos:   usize          = 0;
+    let clear:     Option<usize>  = if config.clear { Some(config.literals()) } else { None };
+    let end:       Option<usize>  = if config.gif { Some(config.literals() + 1) } else { None };
+
+    loop {
+        let (n, start) = width.next(pos);
+        if start + n as usize > bits.len() {
+            if config.gif
+            { return Err(LzwError::Truncated); }
+            return Ok(res);
+        }
+        let code: usize = (0..n as usize).fold(0, |acc, i| acc | (bits[start + i] as usize) << i);
+        pos = start + n as usize;
+
+        if Some(code) == clear {
+            pos  = width.reset(pos);
+            next = config.first();
+            prev = None;
+            continue;
+        }
+        if Some(code) == end
+        { return Ok(res); }
+
+        let begin: usize = res.len();
+        match prev {
+            _ if code < config.literals()               => { res.push(code as u8); }
+            _ if code >= config.first() && code < next  => { expand(&dict, config.literals(), code, &mut res); }
+            // The code that is being defined right now: the previous string plus its own first byte.
+            Some(p) if code == next                     => {
+                expand(&dict, config.literals(), p, &mut res);
+                res.push(res[begin]);
+            }
+            _                                           => { return Err(LzwError::InvalidCode(start)); }
+        }
+
+        if let Some(p) = prev {
+            if next < dict.len() {
+                dict[next] = (p as u16, res[begin]);
+                next += 1;
+            }
+        }
+        prev = Some(code);
+    }
+}
+
+/// Appends the string of a code to `out`.
+fn expand(dict: &[(u16, u8)], literals: usize, code: usize, out: &mut Vec<u8>) {
+    let start:    usize = out.len();
+    let mut code: usize = code;
+    // Entries only refer to codes defined before them, so this ends at a literal.
+    while code >= literals {
+        let (prefix, c) = dict[code];
+        out.push(c);
+        code = prefix as usize;
+    }
+    out.push(code as u8);
+    out[start..].reverse();
+}
+
+/// Packs a `BitVec` into bytes, least significant bit first, padding the last byte with zeros.
+/// # Arguments
+/// * `bits` - The bits to be packed.
+/// # Returns
+/// A `Vec<u8>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::lzw::{pack, unpack};
+/// use bit_vec::BitVec;
+/// let bits = BitVec::from_fn(10, |i| i == 0 || i == 9);
+///
+/// assert_eq!(pack(&bits), vec![0b0000_0001, 0b0000_0010]);
+/// assert_eq!(unpack(&pack(&bits)).len(), 16);
+/// ```
+#[must_use]
+pub fn pack(bits: &BitVec) -> Vec<u8> {
+    bits.to_bytes().iter().map(|b| b.reverse_bits()).collect()
+}
+
+/// Unpacks bytes into a `BitVec`, least significant bit first.
+/// # Arguments
+/// * `bytes` - The bytes to be unpacked.
+/// # Returns
+/// A `BitVec` with `8 * bytes.len()` bits.
+#[must_use]
+pub fn unpack(bytes: &[u8]) -> BitVec {
+    BitVec::from_bytes(&bytes.iter().map(|b| b.reverse_bits()).collect::<Vec<u8>>())
+}
+
+/// Compresses data into the `.Z` format of Unix `compress`.
+/// # Arguments
+/// * `data` - The data to be compressed.
+/// * `max_bits` - The largest code width, `9..=16`. `compress` uses 16 by default.
+/// # Returns
+/// A `Vec<u8>`.
+/// # Panics
+/// Panics if `max_bits` is out of range.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::lzw::{compress_z, decompress_z};
+/// let data = "Lorem ipsum dolor sit amet, Lorem ipsum dolor sit amet.".repeat(10);
+/// let enc  = compress_z(data.as_bytes(), 16);
+///
+/// assert_eq!(enc[0..3], [0x1F, 0x9D, 0x90]);
+/// assert_eq!(decompress_z(&enc).unwrap(), data.as_bytes());
+/// ```
+#[must_use]
+pub fn compress_z(data: &[u8], max_bits: u8) -> Vec<u8> {
+    let config:  LzwConfig = LzwConfig::unix(max_bits);
+    let mut res: Vec<u8>   = vec![Z_MAGIC[0], Z_MAGIC[1], BLOCK_MODE | max_bits];
+    // Every byte is a valid literal with 8 literal bits.
+    res.extend(pack(&encode(data, &config).unwrap()));
+    res
+}
+
+/// Decompresses a `.Z` file, with or without block mode.
+/// # Arguments
+/// * `data` - The compressed data.
+/// # Returns
+/// A `Result<Vec<u8>, LzwError>`.
+pub fn decompress_z(data: &[u8]) -> Result<Vec<u8>, LzwError> {
+    if data.len() < 3 || data[0..2] != Z_MAGIC
+    { return Err(LzwError::InvalidHeader("Not a .Z file.")); }
+    let max_bits: u8 = data[2] & 0x1F;
+    if !(9..=16).contains(&max_bits)
+    { return Err(LzwError::InvalidHeader("The maximum code width must be in the range of 9 to 16.")); }
+
+    let mut config: LzwConfig = LzwConfig::unix(max_bits);
+    config.clear = data[2] & BLOCK_MODE != 0;
+    decode(&unpack(&data[3..]), &config)
+}
+
+/// Compresses the pixels of a GIF image into its image data:
+/// the minimum code size followed by the codes in sub-blocks of up to 255 bytes and an empty block.
+/// # Arguments
+/// * `pixels` - The colour indices of the pixels.
+/// * `min_code_size` - The number of bits per colour index, `2..=8`.
+/// # Returns
+/// A `Result<Vec<u8>, LzwError>`. Fails with `LzwError::InvalidSymbol` if a colour index needs more bits.
+/// # Panics
+/// Panics if `min_code_size` is out of range.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::lzw::{gif_compress, gif_decompress};
+/// let pixels: Vec<u8> = (0..1000).map(|i| (i / 100 % 4) as u8).collect();
+/// let enc = gif_compress(&pixels, 2).unwrap();
+///
+/// assert_eq!(gif_decompress(&enc).unwrap(), pixels);
+/// assert!(gif_compress(&[4], 2).is_err());
+/// ```
+pub fn gif_compress(pixels: &[u8], min_code_size: u8) -> Result<Vec<u8>, LzwError> {
+    let codes:   Vec<u8> = pack(&encode(pixels, &LzwConfig::gif(min_code_size))?);
+    let mut res: Vec<u8> = Vec::with_capacity(codes.len() + codes.len() / 255 + 3);
+    res.push(min_code_size);
+    for block in codes.chunks(255) {
+        res.push(block.len() as u8);
+        res.extend_from_slice(block);
+    }
+    res.push(0);
+    Ok(res)
+}
+
+/// Decompresses the image data of a GIF image.
+/// # Arguments
+/// * `data` - The image data, starting with the minimum code size.
+/// # Returns
+/// A `Result<Vec<u8>, LzwError>` with the colour indices.
+pub fn gif_decompress(data: &[u8]) -> Result<Vec<u8>, LzwError> {
+    let min_code_size: u8 = *data.first().ok_or(LzwError::Truncated)?;
+    if !(2..=8).contains(&min_code_size)
+    { return Err(LzwError::InvalidHeader("The minimum code size must be in the range of 2 to 8.")); }
+
+    let mut codes: Vec<u8> = Vec::with_capacity(data.len());
+    let mut pos:   usize   = 1;
+    loop {
+        let len: usize = *data.get(pos).ok_or(LzwError::Truncated)? as usize;
+        if len == 0
+        { break; }
+        codes.extend_from_slice(data.get(pos + 1..pos + 1 + len).ok_or(LzwError::Truncated)?);
+        pos += 1 + len;
+    }
+    decode(&unpack(&codes), &LzwConfig::gif(min_code_size))
+}
\ No newline at end of file
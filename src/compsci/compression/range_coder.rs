@@ -0,0 +1,592 @@
+//! A range coder, which is arithmetic coding on bytes instead of bits.
+//! Unlike Huffman coding, symbols don't have to be coded with a whole number of bits,
+//! so skewed distributions compress close to their entropy.
+//!
+//! The probabilities come from a `Model`. `StaticModel` uses fixed frequencies, `AdaptiveModel` learns them while coding
+//! and `Order1Model` additionally conditions them on the previous symbol. Any type implementing `Model` can be plugged in.
+//! # Format
+//! Data written by `compress` is laid out as follows. All integers are little endian.
+//! * `[u8; 4]` - The magic number `LRRC`.
+//! * `u8` - The format version.
+//! * `u64` - The length of the uncompressed data.
+//! * `[u8]` - The range coded bytes, with an adaptive order-0 model.
+use core::fmt;
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+/// The magic number every LibRapid range coder stream starts with.
+pub const RANGE_MAGIC: [u8; 4] = *b"LRRC";
+/// The version of the format written by `compress`.
+pub const RANGE_VERSION: u8 = 1;
+/// The largest total frequency a model may have.
+pub const MAX_TOTAL: u32 = 1 << 16;
+const HEADER_LEN: usize = 13;
+/// The range is renormalized whenever it falls below this value.
+const TOP:        u32   = 1 << 24;
+/// The amount an adaptive model adds to the frequency of a coded symbol.
+const INCREMENT:  u32   = 32;
+
+/// The errors that can occur while range decoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeError {
+    /// The header is malformed. Contains the reason.
+    InvalidHeader(&'static str),
+    /// The data ended before all symbols were decoded.
+    Truncated,
+    /// Data encoded from a string does not decode to valid UTF-8.
+    InvalidUtf8,
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeError::InvalidHeader(s) => write!(f, "Invalid header: {}", s),
+            RangeError::Truncated        => write!(f, "The compressed data is truncated."),
+            RangeError::InvalidUtf8      => write!(f, "The decoded data is not valid UTF-8."),
+        }
+    }
+}
+
+impl std::error::Error for RangeError { }
+
+/// A probability model for the range coder. The symbols are `0..n`.
+/// Encoder and decoder must use models which start in the same state.
+pub trait Model {
+    /// The sum of the frequencies of all symbols. Must be in the range of `1` to `MAX_TOTAL`.
+    fn total(&self) -> u32;
+    /// The cumulative frequency of all symbols before `symbol` and the frequency of `symbol`.
+    fn range(&self, symbol: usize) -> (u32, u32);
+    /// Finds the symbol whose cumulative range contains `target`.
+    /// # Returns
+    /// A `(usize, u32, u32)` with the symbol and the same values `range` would return for it.
+    fn find(&self, target: u32) -> (usize, u32, u32);
+    /// Called after every coded symbol. Adaptive and context models update their state here.
+    fn update(&mut self, _symbol: usize) { }
+}
+
+/// A model with fixed frequencies, which have to be known to both encoder and decoder.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::range_coder::{StaticModel, RangeEncoder, RangeDecoder};
+/// let data      = [0, 0, 0, 1, 0, 0, 2, 0];
+/// let mut model = StaticModel::from_frequencies(&[6, 1, 1]);
+///
+/// let mut enc = RangeEncoder::new();
+/// for s in &data {
+///     enc.encode(&mut model, *s);
+/// }
+/// let bytes = enc.finish();
+///
+/// let mut dec = RangeDecoder::new(&bytes).unwrap();
+/// let res: Vec<usize> = (0..data.len()).map(|_| dec.decode(&mut model).unwrap()).collect();
+/// assert_eq!(res, data);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct StaticModel {
+    cumulative: Vec<u32>,
+}
+
+impl StaticModel {
+    /// Creates a new `StaticModel`. The frequencies are scaled down if their sum exceeds `MAX_TOTAL`,
+    /// but symbols that occur keep a non-zero frequency.
+    /// # Arguments
+    /// * `frequencies` - The frequency of each symbol.
+    /// # Returns
+    /// A new `StaticModel`.
+    /// # Panics
+    /// Panics if all frequencies are `0` or if there are more than `MAX_TOTAL / 2` symbols.
+    #[must_use]
+    pub fn from_frequencies(frequencies: &[u64]) -> StaticModel {
+        let sum: u64 = frequencies.iter().sum();
+        if sum == 0
+        { panic!("At least one frequency must be non-zero."); }
+        if frequencies.len() > (MAX_TOTAL / 2) as usize
+        { panic!("A model can have at most MAX_TOTAL / 2 symbols."); }
+
+        // Leaves room for rounding every non-zero frequency up to 1.
+        let budget:         u64      = if sum <= MAX_TOTAL as u64 { sum }
+                                       else { MAX_TOTAL as u64 - frequencies.len() as u64 };
+        let mut cumulative: Vec<u32> = Vec::with_capacity(frequencies.len() + 1);
+        cumulative.push(0);
+        for f in frequencies {
+            let scaled: u64 = match f {
+                0 => 0,
+                _ => (f * budget / sum).max(1),
+            };
+            cumulative.push(cumulative.last().unwrap() + scaled as u32);
+        }
+        StaticModel { cumulative }
+    }
+    /// Creates a new `StaticModel` out of the byte frequencies of `data`.
+    /// # Arguments
+    /// * `data` - The data the model is made for.
+    /// # Returns
+    /// A new `StaticModel` with 256 symbols.
+    /// # Panics
+    /// Panics if `data` is empty.
+    #[must_use]
+    pub fn from_data(data: &[u8]) -> StaticModel {
+        let mut frequencies: [u64; 256] = [0; 256];
+        for b in data
+        { frequencies[*b as usize] += 1; }
+        StaticModel::from_frequencies(&frequencies)
+    }
+    /// Get the (scaled) frequency of each symbol, e.g. to store them alongside the coded data.
+    /// # Returns
+    /// A `Vec<u64>`, which results in the same model when passed to `from_frequencies`.
+    #[must_use]
+    pub fn frequencies(&self) -> Vec<u64> {
+        self.cumulative.windows(2).map(|w| (w[1] - w[0]) as u64).collect()
+    }
+}
+
+impl Model for StaticModel {
+    #[inline]
+    fn total(&self) -> u32 {
+        *self.cumulative.last().unwrap()
+    }
+    #[inline]
+    fn range(&self, symbol: usize) -> (u32, u32) {
+        (self.cumulative[symbol], self.cumulative[symbol + 1] - self.cumulative[symbol])
+    }
+    fn find(&self, target: u32) -> (usize, u32, u32) {
+        let symbol: usize = self.cumulative.partition_point(|c| *c <= target) - 1;
+        let (low, freq) = self.range(symbol);
+        (symbol, low, freq)
+    }
+}
+
+/// An order-0 model which learns the frequencies while coding. Every symbol starts out equally likely.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::range_coder::{AdaptiveModel, RangeEncoder, RangeDecoder};
+/// let data = [3, 3, 3, 3, 0, 3, 3, 3];
+///
+/// let mut model = AdaptiveModel::new(4);
+/// let mut enc   = RangeEncoder::new();
+/// for s in &data {
+///     enc.encode(&mut model, *s);
+/// }
+/// let bytes = enc.finish();
+///
+/// let mut model = AdaptiveModel::new(4);
+/// let mut dec   = RangeDecoder::new(&bytes).unwrap();
+/// let res: Vec<usize> = (0..data.len()).map(|_| dec.decode(&mut model).unwrap()).collect();
+/// assert_eq!(res, data);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AdaptiveModel {
+    frequencies: Vec<u32>,
+    total:       u32,
+}
+
+impl AdaptiveModel {
+    /// Creates a new `AdaptiveModel`.
+    /// # Arguments
+    /// * `symbols` - The number of symbols.
+    /// # Returns
+    /// A new `AdaptiveModel`.
+    /// # Panics
+    /// Panics if `symbols` is `0` or greater than `MAX_TOTAL / 2`.
+    #[must_use]
+    pub fn new(symbols: usize) -> AdaptiveModel {
+        if symbols == 0 || symbols > (MAX_TOTAL / 2) as usize
+        { panic!("The number of symbols must be in the range of 1 to MAX_TOTAL / 2."); }
+        AdaptiveModel { frequencies: vec![1; symbols], total: symbols as u32 }
+    }
+}
+
+impl Model for AdaptiveModel {
+    #[inline]
+    fn total(&self) -> u32 {
+        self.total
+    }
+    #[inline]
+    fn range(&self, symbol: usize) -> (u32, u32) {
+        (self.frequencies[..symbol].iter().sum(), self.frequencies[symbol])
+    }
+    fn find(&self, target: u32) -> (usize, u32, u32) {
+        let mut low: u32 = 0;
+        for (s, f) in self.frequencies.iter().enumerate() {
+            if target < low + f
+            { return (s, low, *f); }
+            low += f;
+        }
+        // `target` is always smaller than the total.
+        unreachable!()
+    }
+    fn update(&mut self, symbol: usize) {
+        self.frequencies[symbol] += INCREMENT;
+        self.total               += INCREMENT;
+        if self.total > MAX_TOTAL {
+            // Halving keeps the model adaptive and the total in range.
+            for f in &mut self.frequencies
+            { *f = f.div_ceil(2); }
+            self.total = self.frequencies.iter().sum();
+        }
+    }
+}
+
+/// An order-1 context model: one `AdaptiveModel` for every possible previous symbol.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::range_coder::{Order1Model, AdaptiveModel, compress_with, decompress_with};
+/// let data = "abababababababababababababababab".repeat(8);
+/// let order0 = compress_with(data.as_bytes(), AdaptiveModel::new(256));
+/// let order1 = compress_with(data.as_bytes(), Order1Model::new(256));
+///
+/// assert!(order1.len() < order0.len());
+/// assert_eq!(decompress_with(&order1, Order1Model::new(256)).unwrap(), data.as_bytes());
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Order1Model {
+    contexts: Vec<AdaptiveModel>,
+    previous: usize,
+}
+
+impl Order1Model {
+    /// Creates a new `Order1Model`.
+    /// # Arguments
+    /// * `symbols` - The number of symbols.
+    /// # Returns
+    /// A new `Order1Model`.
+    /// # Panics
+    /// Panics if `symbols` is `0` or greater than `MAX_TOTAL / 2`.
+    #[must_use]
+    pub fn new(symbols: usize) -> Order1Model {
+        Order1Model { contexts: vec![AdaptiveModel::new(symbols); symbols], previous: 0 }
+    }
+}
+
+impl Model for Order1Model {
+    #[inline]
+    fn total(&self) -> u32 {
+        self.contexts[self.previous].total()
+    }
+    #[inline]
+    fn range(&self, symbol: usize) -> (u32, u32) {
+        self.contexts[self.previous].range(symbol)
+    }
+    #[inline]
+    fn find(&self, target: u32) -> (usize, u32, u32) {
+        self.contexts[self.previous].find(target)
+    }
+    fn update(&mut self, symbol: usize) {
+        self.contexts[self.previous].update(symbol);
+        self.previous = symbol;
+    }
+}
+
+/// Range encodes symbols into bytes.
+pub struct RangeEncoder {
+    low:        u64,
+    range:      u32,
+    cache:      u8,
+    cache_size: u64,
+    out:        Vec<u8>,
+}
+
+impl RangeEncoder {
+    /// Creates a new `RangeEncoder`.
+    /// # Returns
+    /// A new `RangeEncoder`.
+    #[must_use]
+    pub fn new() -> RangeEncoder {
+        RangeEncoder { low: 0, range: u32::MAX, cache: 0, cache_size: 1, out: Vec::new() }
+    }
+    /// Encodes a symbol and updates the model.
+    /// # Arguments
+    /// * `model` - The model the symbol is coded with.
+    /// * `symbol` - The symbol to be encoded.
+    /// # Panics
+    /// Panics if the model assigns `symbol` a frequency of `0`.
+    pub fn encode<M: Model>(&mut self, model: &mut M, symbol: usize) {
+        let (low, freq) = model.range(symbol);
+        if freq == 0
+        { panic!("The model assigns the symbol a frequency of 0."); }
+
+        let r: u32  = self.range / model.total();
+        self.low   += r as u64 * low as u64;
+        self.range  = r * freq;
+        while self.range < TOP {
+            self.range <<= 8;
+            self.shift_low();
+        }
+        model.update(symbol);
+    }
+    /// Flushes the encoder.
+    /// # Returns
+    /// The encoded bytes as a `Vec<u8>`.
+    #[must_use]
+    pub fn finish(mut self) -> Vec<u8> {
+        for _ in 0..5
+        { self.shift_low(); }
+        self.out
+    }
+
+    /// Outputs the top byte of `low`. Bytes which a carry could still change are held back.
+    fn shift_low(&mut self) {
+        if (self.low as u32) < 0xFF00_0000 || self.low >> 32 != 0 {
+            let carry: u8 = (self.low >> 32) as u8;
+            let mut temp: u8 = self.cache;
+            loop {
+                self.out.push(temp.wrapping_add(carry));
+                temp = 0xFF;
+                self.cache_size -= 1;
+                if self.cache_size == 0
+                { break; }
+            }
+            self.cache = (self.low >> 24) as u8;
+        }
+        self.cache_size += 1;
+        self.low         = (self.low & 0x00FF_FFFF) << 8;
+    }
+}
+
+impl Default for RangeEncoder {
+    fn default() -> RangeEncoder {
+        RangeEncoder::new()
+    }
+}
+
+/// Decodes symbols written by a `RangeEncoder`.
+pub struct RangeDecoder<'a> {
+    data:  &'a [u8],
+    pos:   usize,
+    code:  u32,
+    range: u32,
+}
+
+impl<'a> RangeDecoder<'a> {
+    /// Creates a new `RangeDecoder`.
+    /// # Arguments
+    /// * `data` - The encoded bytes.
+    /// # Returns
+    /// A `Result<RangeDecoder, RangeError>`, failing if `data` is shorter than a flushed encoder.
+    pub fn new(data: &'a [u8]) -> Result<RangeDecoder<'a>, RangeError> {
+        if data.len() < 5
+        { return Err(RangeError::Truncated); }
+        // The first byte is always 0.
+        let code: u32 = u32::from_be_bytes(data[1..5].try_into().unwrap());
+        Ok(RangeDecoder { data, pos: 5, code, range: u32::MAX })
+    }
+    /// Decodes a symbol and updates the model.
+    /// # Arguments
+    /// * `model` - The model the symbol was coded with.
+    /// # Returns
+    /// A `Result<usize, RangeError>`. Fails with `RangeError::Truncated` if more data is needed than there is.
+    pub fn decode<M: Model>(&mut self, model: &mut M) -> Result<usize, RangeError> {
+        let total:  u32 = model.total();
+        let r:      u32 = self.range / total;
+        let (symbol, low, freq) = model.find((self.code / r).min(total - 1));
+
+        self.code  -= r * low;
+        self.range  = r * freq;
+        while self.range < TOP {
+            let b: u8 = *self.data.get(self.pos).ok_or(RangeError::Truncated)?;
+            self.code    = (self.code << 8) | b as u32;
+            self.range <<= 8;
+            self.pos    += 1;
+        }
+        model.update(symbol);
+        Ok(symbol)
+    }
+}
+
+/// Range codes bytes with any model of 256 symbols. The model has to start in the same state for decompression.
+/// # Arguments
+/// * `data` - The data to be compressed.
+/// * `model` - The model to compress with.
+/// # Returns
+/// A `Vec<u8>`.
+#[must_use]
+pub fn compress_with<M: Model>(data: &[u8], mut model: M) -> Vec<u8> {
+    let mut enc: RangeEncoder = RangeEncoder::new();
+    for b in data
+    { enc.encode(&mut model, *b as usize); }
+
+    let mut res: Vec<u8> = Vec::with_capacity(HEADER_LEN + data.len() / 2);
+    res.extend_from_slice(&RANGE_MAGIC);
+    res.push(RANGE_VERSION);
+    res.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    res.extend(enc.finish());
+    res
+}
+
+/// Decompresses data compressed by `compress_with`.
+/// # Arguments
+/// * `data` - The compressed data.
+/// * `model` - A model in the same state as the one passed to `compress_with`.
+/// # Returns
+/// A `Result<Vec<u8>, RangeError>`.
+pub fn decompress_with<M: Model>(data: &[u8], mut model: M) -> Result<Vec<u8>, RangeError> {
+    if data.len() < HEADER_LEN || data[0..4] != RANGE_MAGIC
+    { return Err(RangeError::InvalidHeader("Not a LibRapid range coder stream.")); }
+    if data[4] != RANGE_VERSION
+    { return Err(RangeError::InvalidHeader("Unsupported version.")); }
+    let len: usize = u64::from_le_bytes(data[5..HEADER_LEN].try_into().unwrap()) as usize;
+
+    let mut dec: RangeDecoder = RangeDecoder::new(&data[HEADER_LEN..])?;
+    // Every byte of input decodes to at most a few thousand symbols, which bounds the allocation.
+    let mut res: Vec<u8>      = Vec::with_capacity(len.min(data.len().saturating_mul(64)));
+    for _ in 0..len
+    { res.push(dec.decode(&mut model)? as u8); }
+    Ok(res)
+}
+
+/// Compresses bytes with an adaptive order-0 model.
+/// # Arguments
+/// * `data` - The data to be compressed.
+/// # Returns
+/// A `Vec<u8>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::compression::range_coder::{compress, decompress};
+/// use lib_rapid::compsci::compression::huffman::Encode;
+/// // A skewed distribution: Huffman needs at least one bit per symbol.
+/// let data: Vec<u8> = (0..10_000u32).map(|i| if i % 50 == 0 { b'b' } else { b'a' }).collect();
+/// let enc = compress(&data);
+///
+/// assert!(enc.len() * 4 < data.full_encode().0.len() / 8);
+/// assert_eq!(decompress(&enc).unwrap(), data);
+/// ```
+#[must_use]
+pub fn compress(data: &[u8]) -> Vec<u8> {
+    compress_with(data, AdaptiveModel::new(256))
+}
+
+/// Decompresses data compressed by `compress`.
+/// # Arguments
+/// * `data` - The compressed data.
+/// # Returns
+/// A `Result<Vec<u8>, RangeError>`.
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>, RangeError> {
+    decompress_with(data, AdaptiveModel::new(256))
+}
+
+/// Range coded data produced by `Encode::full_encode`. `T` is the type of the symbols that went in.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Encoded<T> {
+    bytes:  Vec<u8>,
+    symbol: PhantomData<T>,
+}
+
+impl<T> Encoded<T> {
+    /// Wraps bytes written by `Encode::full_encode` or `compress`, e.g. after reading them from a file.
+    /// # Arguments
+    /// * `bytes` - The encoded bytes.
+    /// # Returns
+    /// A new `Encoded<T>`.
+    #[must_use]
+    pub fn from_bytes(bytes: Vec<u8>) -> Encoded<T> {
+        Encoded { bytes, symbol: PhantomData }
+    }
+    /// Get the encoded bytes, which are in the format of `compress`.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+    /// Returns the encoded bytes.
+    #[must_use]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Encoding trait for minimal code writing, the range coder counterpart to `huffman::Encode`.
+pub trait Encode {
+    /// The type of the symbols the input consists of.
+    type Symbol;
+    /// Fully encodes a String or byte slice with an adaptive model.
+    /// # Returns
+    /// An `Encoded<Self::Symbol>`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::compression::range_coder::{Encode, Decode};
+    /// let s: &str = "Lorem Ipsum";
+    /// let enc = s.full_encode();
+    /// let dec = enc.full_decode();
+    ///
+    /// assert_eq!(Ok("Lorem Ipsum".to_owned()), dec);
+    /// ```
+    /// ```
+    /// use lib_rapid::compsci::compression::range_coder::{Encode, Decode};
+    /// let bytes: &[u8] = &[0, 159, 146, 150, 0, 0];
+    /// let enc = bytes.full_encode();
+    ///
+    /// assert_eq!(Ok(bytes.to_vec()), enc.full_decode());
+    /// ```
+    #[must_use]
+    fn full_encode(&self) -> Encoded<Self::Symbol>;
+}
+/// Decoding trait for minimal code writing, the range coder counterpart to `huffman::Decode`.
+pub trait Decode {
+    /// The type of the decoded message.
+    type Output;
+    /// Fully decodes an encoded String or byte slice.
+    /// # Returns
+    /// A `Result<String, RangeError>` for `char` input, a `Result<Vec<u8>, RangeError>` for byte input.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::compression::range_coder::{Encoded, Encode, Decode, RangeError};
+    /// let bytes = Encoded::<u8>::from_bytes(vec![0; 4]);
+    /// assert!(matches!(bytes.full_decode(), Err(RangeError::InvalidHeader(_))));
+    ///
+    /// let latin1: &[u8] = b"\xE4";
+    /// let text = Encoded::<char>::from_bytes(latin1.full_encode().into_bytes());
+    /// assert_eq!(text.full_decode(), Err(RangeError::InvalidUtf8));
+    ///
+    /// let mut truncated = "Lorem Ipsum".full_encode().into_bytes();
+    /// truncated.truncate(14);
+    /// assert_eq!(Encoded::<char>::from_bytes(truncated).full_decode(), Err(RangeError::Truncated));
+    /// ```
+    fn full_decode(&self) -> Self::Output;
+}
+
+impl Encode for String {
+    type Symbol = char;
+
+    fn full_encode(&self) -> Encoded<char> {
+        Encoded::from_bytes(compress(self.as_bytes()))
+    }
+}
+
+impl Encode for &str {
+    type Symbol = char;
+
+    fn full_encode(&self) -> Encoded<char> {
+        Encoded::from_bytes(compress(self.as_bytes()))
+    }
+}
+
+impl Encode for &[u8] {
+    type Symbol = u8;
+
+    fn full_encode(&self) -> Encoded<u8> {
+        Encoded::from_bytes(compress(self))
+    }
+}
+
+impl Encode for Vec<u8> {
+    type Symbol = u8;
+
+    fn full_encode(&self) -> Encoded<u8> {
+        Encoded::from_bytes(compress(self))
+    }
+}
+
+impl Decode for Encoded<char> {
+    type Output = Result<String, RangeError>;
+
+    fn full_decode(&self) -> Result<String, RangeError> {
+        String::from_utf8(decompress(&self.bytes)?).map_err(|_| RangeError::InvalidUtf8)
+    }
+}
+
+impl Decode for Encoded<u8> {
+    type Output = Result<Vec<u8>, RangeError>;
+
+    fn full_decode(&self) -> Result<Vec<u8>, RangeError> {
+        decompress(&self.bytes)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+//! Binary-to-text encodings for embedding binary data in text, e.g. in JSON, URLs or e-mails.
+//! All decoders are strict: they return `Err(usize)` with the index of the first invalid character,
+//! in the same way as `StringUtils::is_numeric`. An input which ends too early yields its length.
+/// Implements Base64 (RFC 4648) with the standard and the URL-safe alphabet, with or without padding.
+pub mod base64;
+/// Implements Base32 (RFC 4648), with or without padding.
+pub mod base32;
+/// Implements Base58 with the Bitcoin alphabet, which leaves out the look-alikes `0`, `O`, `I` and `l`.
+pub mod base58;
+/// Implements hexadecimal (Base16) in lower or upper case.
+pub mod hex;
+/// Implements Ascii85, as used by PostScript and PDF, with or without the `<~` and `~>` delimiters.
+pub mod ascii85;
+
+/// A binary-to-text encoding.
+pub trait TextEncoding {
+    /// Encodes bytes into text.
+    /// # Arguments
+    /// * `data` - The bytes to be encoded.
+    /// # Returns
+    /// A `String`.
+    #[must_use]
+    fn encode(&self, data: &[u8]) -> String;
+    /// Decodes text into bytes.
+    /// # Arguments
+    /// * `s` - The text to be decoded.
+    /// # Returns
+    /// A `Result<Vec<u8>, usize>` with the decoded bytes, or the index of the first invalid character.
+    fn decode(&self, s: &str) -> Result<Vec<u8>, usize>;
+}
+
+/// Marks characters which aren't part of an alphabet in a decoding table.
+const INVALID: u8 = 0xFF;
+
+/// Builds the table mapping every byte to its value in `alphabet`.
+const fn decode_table(alphabet: &[u8]) -> [u8; 256] {
+    let mut table: [u8; 256] = [INVALID; 256];
+    let mut i:     usize     = 0;
+    while i < alphabet.len() {
+        table[alphabet[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+/// Encodes `data` with `width` bits per character, padding the output with `=` to a multiple of `pad_to` characters.
+/// `pad_to` is `0` for no padding.
+fn encode_bits(data: &[u8], alphabet: &[u8], width: u32, pad_to: usize) -> String {
+    let mask:     u32    = (1 << width) - 1;
+    let mut res:  String = String::with_capacity((data.len() * 8).div_ceil(width as usize) + pad_to);
+    let mut acc:  u32    = 0;
+    let mut bits: u32    = 0;
+
+    for b in data {
+        acc   = acc << 8 | *b as u32;
+        bits += 8;
+        while bits >= width {
+            bits -= width;
+            res.push(alphabet[((acc >> bits) & mask) as usize] as char);
+        }
+        acc &= (1 << bits) - 1;
+    }
+    if bits > 0
+    { res.push(alphabet[((acc << (width - bits)) & mask) as usize] as char); }
+    if pad_to > 0 {
+        while !res.len().is_multiple_of(pad_to)
+        { res.push('='); }
+    }
+    res
+}
+
+/// Decodes the output of `encode_bits`. Unused bits of the last character have to be zero.
+fn decode_bits(s: &str, table: &[u8; 256], width: u32, pad_to: usize) -> Result<Vec<u8>, usize> {
+    let bytes:    &[u8] = s.as_bytes();
+    // A group holds at least two data characters, so excess `=` are caught as invalid characters.
+    let max_pad:  usize = pad_to.saturating_sub(2);
+    let pad:      usize = bytes.iter().rev().take(max_pad).take_while(|c| **c == b'=').count();
+    let data_len: usize = bytes.len() - pad;
+
+    let mut res:  Vec<u8> = Vec::with_capacity(data_len * width as usize / 8);
+    let mut acc:  u32     = 0;
+    let mut bits: u32     = 0;
+    for (i, c) in bytes[..data_len].iter().enumerate() {
+        let v: u8 = table[*c as usize];
+        if v == INVALID
+        { return Err(i); }
+        acc   = acc << width | v as u32;
+        bits += width;
+        if bits >= 8 {
+            bits -= 8;
+            res.push((acc >> bits) as u8);
+        }
+        acc &= (1 << bits) - 1;
+    }
+
+    // A last character which doesn't complete a byte, or which carries set bits beyond the data.
+    if bits >= width || acc != 0
+    { return Err(data_len - 1); }
+    if pad_to > 0 && !bytes.len().is_multiple_of(pad_to)
+    { return Err(bytes.len()); }
+    Ok(res)
+}
\ No newline at end of file
@@ -0,0 +1,121 @@
+//! Ascii85 encodes every 4 bytes as 5 characters from `!` to `u`, i.e. in base 85. A group of four zero bytes becomes `z`.
+//! A final group of `n` bytes is encoded as `n + 1` characters.
+use std::convert::TryInto;
+
+use super::TextEncoding;
+
+/// Ascii85, optionally enclosed in `<~` and `~>` like in PostScript and PDF (Adobe style).
+/// The Adobe style ignores ASCII whitespace between the delimiters, so line-wrapped text can be decoded as is.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::encoding::{TextEncoding, ascii85::Ascii85};
+/// assert_eq!(Ascii85::PLAIN.encode(b"Man is"), "9jqo^Bla");
+/// assert_eq!(Ascii85::ADOBE.encode(&[0, 0, 0, 0, 1]), "<~z!<~>");
+///
+/// assert_eq!(Ascii85::ADOBE.decode("<~z!<~>").unwrap(), [0, 0, 0, 0, 1]);
+/// assert_eq!(Ascii85::PLAIN.decode("9jqo^Bl{"), Err(7)); // '{' is out of range.
+/// assert_eq!(Ascii85::PLAIN.decode("9jz"), Err(2)); // 'z' in the middle of a group.
+/// assert_eq!(Ascii85::ADOBE.decode("<~9jqo^"), Err(7)); // The end delimiter is missing.
+///
+/// assert_eq!(Ascii85::ADOBE.decode("<~9jqo^\nBla~>").unwrap(), b"Man is");
+/// assert_eq!(Ascii85::ADOBE.decode("<~9j qo^ Bl{~>"), Err(11)); // Positions still count the whitespace.
+/// assert_eq!(Ascii85::PLAIN.decode("9jqo^ Bla"), Err(5));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Ascii85 {
+    delimiters: bool,
+}
+
+impl Ascii85 {
+    /// Ascii85 without delimiters.
+    pub const PLAIN: Ascii85 = Ascii85::new(false);
+    /// Ascii85 enclosed in `<~` and `~>`.
+    pub const ADOBE: Ascii85 = Ascii85::new(true);
+
+    /// Creates a new `Ascii85` variant.
+    /// # Arguments
+    /// * `delimiters` - Whether the text is enclosed in `<~` and `~>`. If so, the decoder requires them and skips ASCII whitespace.
+    /// # Returns
+    /// A new `Ascii85`.
+    #[must_use]
+    pub const fn new(delimiters: bool) -> Ascii85 {
+        Ascii85 { delimiters }
+    }
+}
+
+impl TextEncoding for Ascii85 {
+    fn encode(&self, data: &[u8]) -> String {
+        let mut res: String = String::with_capacity(data.len() * 5 / 4 + 5);
+        if self.delimiters
+        { res.push_str("<~"); }
+
+        for chunk in data.chunks(4) {
+            let mut group: [u8; 4] = [0; 4];
+            group[..chunk.len()].copy_from_slice(chunk);
+            let mut v: u32 = u32::from_be_bytes(group);
+            if v == 0 && chunk.len() == 4 {
+                res.push('z');
+                continue;
+            }
+
+            let mut digits: [u8; 5] = [0; 5];
+            for d in digits.iter_mut().rev() {
+                *d = (v % 85) as u8 + b'!';
+                v /= 85;
+            }
+            res.extend(digits[..chunk.len() + 1].iter().map(|d| *d as char));
+        }
+
+        if self.delimiters
+        { res.push_str("~>"); }
+        res
+    }
+    fn decode(&self, s: &str) -> Result<Vec<u8>, usize> {
+        let bytes:  &[u8] = s.as_bytes();
+        let closed: bool  = bytes.len() >= 4 && bytes.ends_with(b"~>");
+        let (start, end)  = match self.delimiters {
+            true if !bytes.starts_with(b"<~") => { return Err(if bytes.first() == Some(&b'<') { 1 } else { 0 }); }
+            // A `~` anywhere else is reported as an invalid character.
+            true if closed                    => (2, bytes.len() - 2),
+            true                              => (2, bytes.len()),
+            false                             => (0, bytes.len()),
+        };
+
+        let mut res:   Vec<u8> = Vec::with_capacity((end - start) * 4 / 5 + 4);
+        let mut group: u64     = 0;
+        let mut count: usize   = 0;
+        // The position of the last digit, which errors in the final group are reported at.
+        let mut last:  usize   = start;
+        for (i, c) in bytes.iter().enumerate().take(end).skip(start) {
+            match c {
+                b'z' if count == 0                                => { res.extend_from_slice(&[0; 4]); }
+                c if self.delimiters && c.is_ascii_whitespace()   => { }
+                b'!'..=b'u'                                       => {
+                    group  = group * 85 + (c - b'!') as u64;
+                    count += 1;
+                    last   = i;
+                    if count == 5 {
+                        let v: u32 = group.try_into().map_err(|_| i)?;
+                        res.extend_from_slice(&v.to_be_bytes());
+                        group = 0;
+                        count = 0;
+                    }
+                }
+                _                                                 => { return Err(i); }
+            }
+        }
+
+        if count == 1
+        { return Err(last); }
+        if count > 1 {
+            // Padding with the highest digit rounds the truncated bytes back up.
+            for _ in count..5
+            { group = group * 85 + 84; }
+            let v: u32 = group.try_into().map_err(|_| last)?;
+            res.extend_from_slice(&v.to_be_bytes()[..count - 1]);
+        }
+        if self.delimiters && !closed
+        { return Err(bytes.len()); }
+        Ok(res)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,50 @@
+//! Base32 encodes every 5 bytes as 8 characters of 5 bits each. Its alphabet only has upper case letters and digits.
+use super::{TextEncoding, decode_table, encode_bits, decode_bits};
+
+const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+static TABLE:   [u8; 256] = decode_table(ALPHABET);
+
+/// A Base32 variant.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::encoding::{TextEncoding, base32::Base32};
+/// assert_eq!(Base32::PADDED.encode(b"foobar"), "MZXW6YTBOI======");
+/// assert_eq!(Base32::UNPADDED.encode(b"foobar"), "MZXW6YTBOI");
+///
+/// assert_eq!(Base32::PADDED.decode("MZXW6YTBOI======").unwrap(), b"foobar");
+/// assert_eq!(Base32::PADDED.decode("MZXW6ytBOI======"), Err(5)); // Lower case is invalid.
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Base32 {
+    padding: bool,
+}
+
+impl Base32 {
+    /// Base32 padded with `=` to a multiple of 8 characters.
+    pub const PADDED:   Base32 = Base32::new(true);
+    /// Base32 without padding.
+    pub const UNPADDED: Base32 = Base32::new(false);
+
+    /// Creates a new `Base32` variant.
+    /// # Arguments
+    /// * `padding` - Whether the output is padded to a multiple of 8 characters. If so, the decoder requires the padding, else it rejects it.
+    /// # Returns
+    /// A new `Base32`.
+    #[must_use]
+    pub const fn new(padding: bool) -> Base32 {
+        Base32 { padding }
+    }
+
+    const fn pad_to(&self) -> usize {
+        if self.padding { 8 } else { 0 }
+    }
+}
+
+impl TextEncoding for Base32 {
+    fn encode(&self, data: &[u8]) -> String {
+        encode_bits(data, ALPHABET, 5, self.pad_to())
+    }
+    fn decode(&self, s: &str) -> Result<Vec<u8>, usize> {
+        decode_bits(s, &TABLE, 5, self.pad_to())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,69 @@
+//! Base58 treats the data as one big number and writes it in base 58. Leading zero bytes become leading `1`s.
+//! The conversion takes quadratic time, so Base58 is meant for short data such as keys and addresses.
+use super::{TextEncoding, INVALID, decode_table};
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+static TABLE:   [u8; 256] = decode_table(ALPHABET);
+
+/// Base58 with the Bitcoin alphabet.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::encoding::{TextEncoding, base58::Base58};
+/// assert_eq!(Base58.encode(b"Hello World!"), "2NEpo7TZRRrLZSi2U");
+/// assert_eq!(Base58.encode(&[0, 0, 0x28, 0x7F]), "1145k");
+///
+/// assert_eq!(Base58.decode("1145k").unwrap(), [0, 0, 0x28, 0x7F]);
+/// assert_eq!(Base58.decode("2NEpo0TZ"), Err(5)); // '0' is not part of the alphabet.
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Base58;
+
+impl TextEncoding for Base58 {
+    fn encode(&self, data: &[u8]) -> String {
+        let zeros:      usize   = data.iter().take_while(|b| **b == 0).count();
+        // The digits in base 58, least significant first.
+        let mut digits: Vec<u8> = Vec::with_capacity(data.len() * 138 / 100 + 1);
+        for b in &data[zeros..] {
+            let mut carry: u32 = *b as u32;
+            for d in digits.iter_mut() {
+                carry += (*d as u32) << 8;
+                *d     = (carry % 58) as u8;
+                carry /= 58;
+            }
+            while carry > 0 {
+                digits.push((carry % 58) as u8);
+                carry /= 58;
+            }
+        }
+
+        let mut res: String = String::with_capacity(zeros + digits.len());
+        res.extend(std::iter::repeat_n('1', zeros));
+        res.extend(digits.iter().rev().map(|d| ALPHABET[*d as usize] as char));
+        res
+    }
+    fn decode(&self, s: &str) -> Result<Vec<u8>, usize> {
+        let ones:      usize   = s.bytes().take_while(|c| *c == b'1').count();
+        // The bytes, least significant first.
+        let mut bytes: Vec<u8> = Vec::with_capacity(s.len() * 733 / 1000 + 1);
+        for (i, c) in s.bytes().enumerate().skip(ones) {
+            let v: u8 = TABLE[c as usize];
+            if v == INVALID
+            { return Err(i); }
+
+            let mut carry: u32 = v as u32;
+            for b in bytes.iter_mut() {
+                carry += *b as u32 * 58;
+                *b     = carry as u8;
+                carry >>= 8;
+            }
+            while carry > 0 {
+                bytes.push(carry as u8);
+                carry >>= 8;
+            }
+        }
+
+        let mut res: Vec<u8> = vec![0; ones];
+        res.extend(bytes.iter().rev());
+        Ok(res)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,62 @@
+//! Base64 encodes every 3 bytes as 4 characters of 6 bits each.
+use super::{TextEncoding, decode_table, encode_bits, decode_bits};
+
+const STANDARD_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+static STANDARD_TABLE:   [u8; 256] = decode_table(STANDARD_ALPHABET);
+static URL_SAFE_TABLE:   [u8; 256] = decode_table(URL_SAFE_ALPHABET);
+
+/// A Base64 variant.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::encoding::{TextEncoding, base64::Base64};
+/// assert_eq!(Base64::STANDARD.encode(b"Many hands"), "TWFueSBoYW5kcw==");
+/// assert_eq!(Base64::URL_SAFE_NO_PAD.encode(&[0xFB, 0xFF]), "-_8");
+///
+/// assert_eq!(Base64::STANDARD.decode("TWFueSBoYW5kcw==").unwrap(), b"Many hands");
+/// assert_eq!(Base64::STANDARD.decode("TWFue$Bo"), Err(5)); // Invalid character.
+/// assert_eq!(Base64::STANDARD.decode("TWFueSBoYW5kcw"), Err(14)); // Padding is missing.
+/// assert_eq!(Base64::STANDARD_NO_PAD.decode("TWFueSBoYW5kcw").unwrap(), b"Many hands");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Base64 {
+    url_safe: bool,
+    padding:  bool,
+}
+
+impl Base64 {
+    /// The standard alphabet with `+` and `/`, padded with `=`.
+    pub const STANDARD:        Base64 = Base64::new(false, true);
+    /// The standard alphabet without padding.
+    pub const STANDARD_NO_PAD: Base64 = Base64::new(false, false);
+    /// The URL- and filename-safe alphabet with `-` and `_`, padded with `=`.
+    pub const URL_SAFE:        Base64 = Base64::new(true, true);
+    /// The URL- and filename-safe alphabet without padding.
+    pub const URL_SAFE_NO_PAD: Base64 = Base64::new(true, false);
+
+    /// Creates a new `Base64` variant.
+    /// # Arguments
+    /// * `url_safe` - Whether to use `-` and `_` instead of `+` and `/`.
+    /// * `padding` - Whether the output is padded to a multiple of 4 characters. If so, the decoder requires the padding, else it rejects it.
+    /// # Returns
+    /// A new `Base64`.
+    #[must_use]
+    pub const fn new(url_safe: bool, padding: bool) -> Base64 {
+        Base64 { url_safe, padding }
+    }
+
+    const fn pad_to(&self) -> usize {
+        if self.padding { 4 } else { 0 }
+    }
+}
+
+impl TextEncoding for Base64 {
+    fn encode(&self, data: &[u8]) -> String {
+        let alphabet: &[u8; 64] = if self.url_safe { URL_SAFE_ALPHABET } else { STANDARD_ALPHABET };
+        encode_bits(data, alphabet, 6, self.pad_to())
+    }
+    fn decode(&self, s: &str) -> Result<Vec<u8>, usize> {
+        let table: &[u8; 256] = if self.url_safe { &URL_SAFE_TABLE } else { &STANDARD_TABLE };
+        decode_bits(s, table, 6, self.pad_to())
+    }
+}
\ No newline at end of file
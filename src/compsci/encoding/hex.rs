@@ -0,0 +1,60 @@
+//! Hexadecimal encodes every byte as two characters of 4 bits each.
+use super::{TextEncoding, INVALID, encode_bits, decode_bits};
+
+const LOWER: &[u8; 16] = b"0123456789abcdef";
+const UPPER: &[u8; 16] = b"0123456789ABCDEF";
+static TABLE: [u8; 256] = hex_table();
+
+/// Accepts both cases.
+const fn hex_table() -> [u8; 256] {
+    let mut table: [u8; 256] = [INVALID; 256];
+    let mut i:     usize     = 0;
+    while i < 16 {
+        table[LOWER[i] as usize] = i as u8;
+        table[UPPER[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+/// Hexadecimal in lower or upper case. Decoding accepts both cases, even mixed.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::encoding::{TextEncoding, hex::Hex};
+/// assert_eq!(Hex::LOWER.encode(&[0xDE, 0xAD, 0xBE, 0xEF]), "deadbeef");
+/// assert_eq!(Hex::UPPER.encode(&[0xDE, 0xAD, 0xBE, 0xEF]), "DEADBEEF");
+///
+/// assert_eq!(Hex::LOWER.decode("DeadBeef").unwrap(), [0xDE, 0xAD, 0xBE, 0xEF]);
+/// assert_eq!(Hex::LOWER.decode("deadbeeg"), Err(7));
+/// assert_eq!(Hex::LOWER.decode("abc"), Err(2)); // Odd length.
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Hex {
+    upper_case: bool,
+}
+
+impl Hex {
+    /// Hexadecimal with lower case letters.
+    pub const LOWER: Hex = Hex::new(false);
+    /// Hexadecimal with upper case letters.
+    pub const UPPER: Hex = Hex::new(true);
+
+    /// Creates a new `Hex` variant.
+    /// # Arguments
+    /// * `upper_case` - Whether to encode with upper case letters.
+    /// # Returns
+    /// A new `Hex`.
+    #[must_use]
+    pub const fn new(upper_case: bool) -> Hex {
+        Hex { upper_case }
+    }
+}
+
+impl TextEncoding for Hex {
+    fn encode(&self, data: &[u8]) -> String {
+        encode_bits(data, if self.upper_case { UPPER } else { LOWER }, 4, 0)
+    }
+    fn decode(&self, s: &str) -> Result<Vec<u8>, usize> {
+        decode_bits(s, &TABLE, 4, 0)
+    }
+}
\ No newline at end of file
@@ -1,20 +1,110 @@
 //! Basic logging functionality.
-use chrono::{Utc, SecondsFormat};
-use std::{io::prelude::*, fs::OpenOptions};
+use chrono::{DateTime, Utc};
+use std::{io::prelude::*, fs::OpenOptions, fmt, str::FromStr, sync::Arc};
 use crate::math::general::NumTools;
+use format::{Format, Record, Value};
+use rotation::Rotation;
+use sink::Sink;
+use timestamp::{Clock, Timestamp};
+/// Implements the output formats plain text, logfmt and JSON lines, and the key/value fields of records.
+pub mod format;
+/// Implements the rotation of log files by size or time, optionally compressing rotated files.
+pub mod rotation;
+/// Implements `AsyncLogger`, which hands records to a writer thread and can be shared between threads.
+pub mod background;
+/// Implements the `Sink` trait for additional outputs, and sinks for stderr, files, in-memory ring buffers and any `std::io::Write`.
+pub mod sink;
+/// Implements configurable timestamps and the `Clock` trait for taking the time from somewhere else than the system clock.
+pub mod timestamp;
+/// Installs a `Logger` as the global logger of the `log` crate, so the `log::info!` etc. macros of any crate end up in it.
+#[cfg(feature = "log")]
+pub mod facade;
+
+/// The severity of a log message, from the least to the most severe.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::rapidlogging::Level;
+/// assert!(Level::Debug < Level::Warn);
+/// assert_eq!(Level::Warn.to_string(), "WARN");
+/// assert_eq!("warning".parse(), Ok(Level::Warn));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// Very detailed information, e.g. for following the control flow.
+    Trace,
+    /// Information which is only of interest while debugging.
+    Debug,
+    /// General information about the normal operation.
+    Info,
+    /// Something unexpected which can be recovered from.
+    Warn,
+    /// A failure.
+    Error,
+}
+
+impl Level {
+    /// Get the name of the level in upper case, as used in the log output.
+    /// # Returns
+    /// A `&'static str`.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Level::Trace => "TRACE",
+            Level::Debug => "DEBUG",
+            Level::Info  => "INFO",
+            Level::Warn  => "WARN",
+            Level::Error => "ERROR",
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+    /// Parses a level case-insensitively. `"warning"` is accepted as well as `"warn"`.
+    fn from_str(s: &str) -> Result<Level, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "trace"             => Ok(Level::Trace),
+            "debug"             => Ok(Level::Debug),
+            "info"              => Ok(Level::Info),
+            "warn" | "warning"  => Ok(Level::Warn),
+            "error"             => Ok(Level::Error),
+            _                   => Err(format!("Unknown log level: {:?}", s)),
+        }
+    }
+}
 
 /// The structure for the RapidLogger.
 pub struct Logger {
     /// The buffer size. If the buffer count exceeds this value, the buffer gets written to the specified file.
+    /// A buffer which can't be written is kept for another try until it holds twice as many lines, then it is dropped.
     pub buff_size:      usize,
         buff_count:     usize,
         buffer:         String,
+        file_sent:      usize,
+        console_sent:   usize,
     /// Determines wether to write to the console.
     pub log_to_console: bool,
     /// Determines wether to write to a file.
     pub log_to_file:    bool,
     /// The optional file path.
     pub file_path:      Option<String>,
+    /// Messages below this level are dropped. `Level::Trace` by default, which lets everything through.
+    pub min_level:      Level,
+    /// The output format. `Format::Text` by default.
+    pub format:         Format,
+    /// The optional rotation of the log file. `None` by default, which appends to the file forever.
+    pub rotation:       Option<Rotation>,
+        period:         Option<i64>,
+        sinks:          Vec<Box<dyn Sink>>,
+    /// How timestamps are written. RFC 3339 in UTC with whole seconds by default.
+    pub timestamp:      Timestamp,
+        clock:          Option<Arc<dyn Clock>>,
 }
 
 impl Logger {
@@ -26,11 +116,20 @@ impl Logger {
                file_path:      Option<String>)
                -> Logger {
                 Logger { buff_size,
-                         buffer:     String::new(),
-                         buff_count: 0,
+                         buffer:       String::new(),
+                         buff_count:   0,
+                         file_sent:    0,
+                         console_sent: 0,
                          log_to_console,
                          log_to_file,
-                         file_path }
+                         file_path,
+                         min_level:    Level::Trace,
+                         format:       Format::Text,
+                         rotation:     None,
+                         period:       None,
+                         sinks:        Vec::new(),
+                         timestamp:    Timestamp::new_default(),
+                         clock:        None }
     }
     /// Creates a new `Logger` object with default values.
     #[must_use]
@@ -38,11 +137,20 @@ impl Logger {
         Logger { buff_size:      10,
                  buff_count:     0,
                  buffer:         String::new(),
+                 file_sent:      0,
+                 console_sent:   0,
                  log_to_console: true,
                  log_to_file:    false,
-                 file_path:      None }
+                 file_path:      None,
+                 min_level:      Level::Trace,
+                 format:         Format::Text,
+                 rotation:       None,
+                 period:         None,
+                 sinks:          Vec::new(),
+                 timestamp:      Timestamp::new_default(),
+                 clock:          None }
     }
-    /// Logs to a `Logger`.
+    /// Logs to a `Logger`. Messages logged this way carry no level and are never filtered by `min_level`; see `log_at`.
     /// # Returns
     /// A `Result<(), String>`. `()` if it was successful, otherwise the error message as a `String`.
     /// # Examples
@@ -56,53 +164,258 @@ impl Logger {
     /// ```
     /// As you can see, we initialise a new Logger `l` with the buffer size 3. This means that only after 3x logging, the logger writes to the file and to the console.
     pub fn log(&mut self, prefixes: Option<Vec<&str>>, msg: &str) -> Result<(), String> {
-        self.buff_count.inc();
-        let mut out: String = format!("[{}]", Utc::now()
-                                                   .to_rfc3339_opts(SecondsFormat::Secs,
-                                                                    true));
-        if let Some(v) = prefixes {
-            for s in v
-            { out.push_str(&format!("[{}]", s)); }
-        }
-        out.push(' ');
-        out.push_str(msg);
-        out.push('\n');
-        self.buffer.push_str(&out);
-
-        if self.buff_count == self.buff_size
-        { return self.backend_log(); }
-
-        Ok(())
+        self.push_line(None, prefixes, &msg, &[], self.now())
+    }
+    /// Checks whether messages of a level get logged.
+    /// Use this to skip building expensive messages which would be dropped anyway.
+    /// # Arguments
+    /// * `level` - The level.
+    /// # Returns
+    /// A `bool`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::rapidlogging::{Logger, Level};
+    /// let mut l: Logger = Logger::new_default();
+    /// l.min_level = Level::Info;
+    ///
+    /// assert!(!l.is_enabled(Level::Debug));
+    /// assert!(l.is_enabled(Level::Error));
+    /// ```
+    #[must_use]
+    pub fn is_enabled(&self, level: Level) -> bool {
+        level >= self.min_level
+    }
+    /// Logs a message with a level to a `Logger`. The level is written after the timestamp, followed by the prefixes.
+    /// Messages below `min_level` are dropped before anything is formatted, so they neither fill the buffer nor cause a write.
+    /// # Arguments
+    /// * `level` - The level of the message.
+    /// * `prefixes` - Optional prefixes, written in square brackets after the level.
+    /// * `msg` - The message. Anything implementing `Display`; pass `format_args!` to format only if the message gets logged.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if it was successful, otherwise the error message as a `String`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::rapidlogging::{Logger, Level};
+    /// let mut l: Logger = Logger::new(1, true, false, None);
+    /// l.min_level = Level::Warn;
+    /// let _ = l.log_at(Level::Debug, None, "Not logged.");
+    /// let _ = l.log_at(Level::Warn, Some(vec!["Net"]), format_args!("Retrying in {} s.", 5));
+    /// ```
+    /// Prints something like `[2022-01-01T12:00:00Z][WARN][Net] Retrying in 5 s.`.
+    pub fn log_at<D: fmt::Display>(&mut self, level: Level, prefixes: Option<Vec<&str>>, msg: D) -> Result<(), String> {
+        if !self.is_enabled(level)
+        { return Ok(()); }
+        self.push_line(Some(level), prefixes, &msg, &[], self.now())
+    }
+    /// Logs a message with a level and key/value fields to a `Logger`. How the fields are written depends on `format`.
+    /// # Arguments
+    /// * `level` - The level of the message.
+    /// * `msg` - The message. Anything implementing `Display`.
+    /// * `fields` - The fields as pairs of key and value. Values can be created from strings, numbers and `bool`s with `into()`.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if it was successful, otherwise the error message as a `String`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::rapidlogging::{Logger, Level, format::Format};
+    /// let mut l: Logger = Logger::new(1, true, false, None);
+    /// l.format = Format::JsonLines;
+    /// let _ = l.log_with(Level::Info, "Request served.", &[("status", 200.into()),
+    ///                                                      ("path", "/index.html".into())]);
+    /// ```
+    /// Prints something like `{"ts":"2022-01-01T12:00:00Z","level":"INFO","msg":"Request served.","status":200,"path":"/index.html"}`.
+    pub fn log_with<D: fmt::Display>(&mut self, level: Level, msg: D, fields: &[(&str, Value)]) -> Result<(), String> {
+        if !self.is_enabled(level)
+        { return Ok(()); }
+        self.push_line(Some(level), None, &msg, fields, self.now())
+    }
+    /// Logs a message with `Level::Trace`. See `log_at`.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if it was successful, otherwise the error message as a `String`.
+    pub fn trace<D: fmt::Display>(&mut self, msg: D) -> Result<(), String> {
+        self.log_at(Level::Trace, None, msg)
+    }
+    /// Logs a message with `Level::Debug`. See `log_at`.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if it was successful, otherwise the error message as a `String`.
+    pub fn debug<D: fmt::Display>(&mut self, msg: D) -> Result<(), String> {
+        self.log_at(Level::Debug, None, msg)
+    }
+    /// Logs a message with `Level::Info`. See `log_at`.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if it was successful, otherwise the error message as a `String`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::rapidlogging::Logger;
+    /// let mut l: Logger = Logger::new_default();
+    /// let _ = l.info("Started.");
+    /// let _ = l.warn(format_args!("{} retries left.", 2));
+    /// let _ = l.error("Giving up.");
+    /// ```
+    pub fn info<D: fmt::Display>(&mut self, msg: D) -> Result<(), String> {
+        self.log_at(Level::Info, None, msg)
+    }
+    /// Logs a message with `Level::Warn`. See `log_at`.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if it was successful, otherwise the error message as a `String`.
+    pub fn warn<D: fmt::Display>(&mut self, msg: D) -> Result<(), String> {
+        self.log_at(Level::Warn, None, msg)
+    }
+    /// Logs a message with `Level::Error`. See `log_at`.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if it was successful, otherwise the error message as a `String`.
+    pub fn error<D: fmt::Display>(&mut self, msg: D) -> Result<(), String> {
+        self.log_at(Level::Error, None, msg)
+    }
+    /// Adds a sink, which receives every record that passes `min_level` in addition to the console and the file.
+    /// Set `log_to_console` and `log_to_file` to `false` to only log to sinks.
+    /// # Arguments
+    /// * `sink` - The sink.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::rapidlogging::{Logger, Level, format::Format, sink::{RingBufferSink, StderrSink}};
+    /// let ring: RingBufferSink = RingBufferSink::new(100, Level::Trace, Format::Logfmt);
+    /// let mut l: Logger = Logger::new_default();
+    /// l.add_sink(ring.clone());
+    /// l.add_sink(StderrSink::new(Level::Warn, Format::Text));
+    ///
+    /// let _ = l.log_with(Level::Info, "Cache hit.", &[("key", "a".into())]);
+    /// assert!(ring.lines()[0].ends_with("level=INFO msg=\"Cache hit.\" key=a"));
+    /// ```
+    pub fn add_sink<S: Sink + 'static>(&mut self, sink: S) {
+        self.sinks.push(Box::new(sink));
+    }
+    /// Replaces the system clock, e.g. by a `timestamp::ManualClock` for deterministic timestamps in tests.
+    /// The clock is also used for time-based rotation, both of the log file and of sinks like `sink::FileSink`.
+    /// # Arguments
+    /// * `clock` - The clock.
+    pub fn set_clock<C: Clock + 'static>(&mut self, clock: C) {
+        self.clock = Some(Arc::new(clock));
+    }
+    /// Get the current time from the clock.
+    fn now(&self) -> DateTime<Utc> {
+        self.clock.as_ref().map_or_else(Utc::now, |c| c.now())
+    }
+    /// Writes out the buffer and flushes the sinks right away, even if the buffer isn't full yet. Does nothing if the buffer is empty.
+    /// Dropping a `Logger` flushes it as well, ignoring errors.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if it was successful, otherwise the error message as a `String`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::rapidlogging::Logger;
+    /// let mut l: Logger = Logger::new(100, true, false, None);
+    /// let _ = l.info("Printed by flush instead of after 100 messages.");
+    /// assert!(l.flush().is_ok());
+    ///
+    /// // Logging to a file without a path fails instead of panicking.
+    /// let mut l: Logger = Logger::new(1, false, true, None);
+    /// assert!(l.log(None, "Kept for another try.").is_err());
+    /// assert_eq!(l.log(None, "Lost."), Err("No file path given to log to. Dropped the buffer of 2 lines.".to_string()));
+    /// assert!(l.flush().is_ok());
+    /// ```
+    pub fn flush(&mut self) -> Result<(), String> {
+        if self.buff_count == 0
+        { return Ok(()); }
+        self.backend_log()
     }
     /// Resets `buffer` and `buff_counter`.
     pub fn reset_buffs(&mut self) {
-        self.buff_count = 0;
-        self.buffer = String::new();
+        self.buff_count   = 0;
+        self.buffer       = String::new();
+        self.file_sent    = 0;
+        self.console_sent = 0;
+    }
+    /// Formats a line into the buffer and writes the buffer out once it is full.
+    fn push_line(&mut self,
+                 level:    Option<Level>,
+                 prefixes: Option<Vec<&str>>,
+                 msg:      &dyn fmt::Display,
+                 fields:   &[(&str, Value)],
+                 time:     DateTime<Utc>)
+                 -> Result<(), String> {
+        self.buff_count.inc();
+        let timestamp: String     = self.timestamp.format(time);
+        let prefixes:  Vec<&str>  = prefixes.unwrap_or_default();
+        let message:   String     = msg.to_string();
+        let record:    Record<'_> = Record { timestamp: &timestamp,
+                                             level,
+                                             prefixes:  &prefixes,
+                                             message:   &message,
+                                             fields };
+        if self.log_to_console || self.log_to_file
+        { self.buffer.push_str(&self.format.format(&record)); }
+
+        let mut res: Result<(), String> = Ok(());
+        for s in self.sinks.iter_mut().filter(|s| level.is_none_or(|l| l >= s.min_level()))
+        { res = res.and(s.log(&record)); }
+
+        // `>=`, so a buffer which couldn't be written is retried with the next line.
+        if self.buff_count >= self.buff_size
+        { return res.and(self.backend_log()); }
+
+        res
     }
     /// For cleaner code, the main functionality is hidden from the user in this function.
     fn backend_log(&mut self) -> Result<(), String> {
-        if self.log_to_file { 
-            let file = OpenOptions::new()
-                                    .create(true)
-                                    .append(true)
-                                    .open(&self.file_path.as_ref().unwrap());
-            let mut file = match file {
-                Ok(f)  => f,
-                Err(e) => { return Err(format!("Problem opening or creating file: {:?}", e)); }
-            };
-            match write!(file, "{}", self.buffer) {
-                Ok(_)  => { }
-                Err(e) => { return Err(format!("Problem writing to file: {:?}", e)); }
+        let now:     DateTime<Utc>      = self.now();
+        let mut res: Result<(), String> = Ok(());
+        for s in self.sinks.iter_mut()
+        { res = res.and(s.flush(now)); }
+
+        // Every output remembers how much of the buffer it got, so a retry only repeats the failed ones.
+        let mut out: Result<(), String> = Ok(());
+        if self.log_to_file && self.file_sent < self.buffer.len() {
+            match self.write_file(now) {
+                Ok(()) => { self.file_sent = self.buffer.len(); }
+                Err(e) => { out = Err(e); }
             }
-            
+        }
+        if self.log_to_console && self.console_sent < self.buffer.len() {
+            let mut stdout = std::io::stdout();
+            match write!(stdout, "{}", &self.buffer[self.console_sent..]).and_then(|_| stdout.flush()) {
+                Ok(_)  => { self.console_sent = self.buffer.len(); }
+                Err(e) => { out = out.and(Err(format!("Problem writing to console: {:?}", e))); }
+            }
+        }
+
+        if out.is_ok()
+        { self.reset_buffs(); }
+        else if self.buff_count >= self.buff_size.saturating_mul(2) {
+            let lost: usize = self.buff_count;
+            self.reset_buffs();
+            out = out.map_err(|e| format!("{} Dropped the buffer of {} lines.", e, lost));
         }
 
-        if self.log_to_console {
-            print!("{}", self.buffer);
-            std::io::stdout().flush().unwrap();
+        res.and(out)
+    }
+    /// Appends the part of the buffer the file hasn't got yet, rotating the file first if due.
+    fn write_file(&mut self, now: DateTime<Utc>) -> Result<(), String> {
+        let path: &String = match &self.file_path {
+            Some(p) => p,
+            None    => { return Err("No file path given to log to.".to_string()); }
+        };
+        let unsent: &str = &self.buffer[self.file_sent..];
+        if let Some(r) = self.rotation {
+            if let Err(e) = r.apply(path, unsent.len() as u64, &mut self.period, now)
+            { return Err(format!("Problem rotating file: {:?}", e)); }
+        }
+        let file = OpenOptions::new()
+                                .create(true)
+                                .append(true)
+                                .open(path);
+        let mut file = match file {
+            Ok(f)  => f,
+            Err(e) => { return Err(format!("Problem opening or creating file: {:?}", e)); }
+        };
+        match write!(file, "{}", unsent) {
+            Ok(_)  => Ok(()),
+            Err(e) => Err(format!("Problem writing to file: {:?}", e)),
         }
-        self.reset_buffs();
+    }
+}
 
-        Ok(())
+impl Drop for Logger {
+    /// Flushes the buffer, so nothing gets lost when the logger goes out of scope or a panic unwinds the stack.
+    fn drop(&mut self) {
+        let _ = self.flush();
     }
 }
\ No newline at end of file
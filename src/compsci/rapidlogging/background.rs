@@ -0,0 +1,195 @@
+//! Logging through a writer thread. The calling threads only filter and format the message and send it over a channel,
+//! while the thread owns the `Logger` and does the buffering and writing.
+use std::fmt;
+use std::sync::{Arc, atomic::{AtomicU8, Ordering}, mpsc::{self, Receiver, Sender}};
+use std::thread::{self, JoinHandle};
+use chrono::{DateTime, Utc};
+use super::{Level, Logger, format::Value, timestamp::Clock};
+
+/// The answer of the writer thread to a flush.
+type Reply = Result<(), String>;
+
+/// A message to the writer thread.
+enum Command {
+    Line { level:    Option<Level>,
+           prefixes: Option<Vec<String>>,
+           msg:      String,
+           keys:     Vec<String>,
+           values:   Vec<Value>,
+           time:     DateTime<Utc> },
+    Flush(Sender<Reply>),
+}
+
+/// The state shared by all clones of an `AsyncLogger`. Dropping it stops the writer thread.
+struct Shared {
+    sender:    Option<Sender<Command>>,
+    thread:    Option<JoinHandle<()>>,
+    min_level: AtomicU8,
+    clock:     Option<Arc<dyn Clock>>,
+}
+
+impl Drop for Shared {
+    fn drop(&mut self) {
+        // Closing the channel ends the thread, which drops and thereby flushes the `Logger`.
+        self.sender.take();
+        if let Some(t) = self.thread.take()
+        { let _ = t.join(); }
+    }
+}
+
+/// A handle to a `Logger` running on its own writer thread.
+/// Cloning it is cheap, and it is `Send + Sync`, so any thread can log through it.
+/// The writer thread stops, flushing the `Logger`, once the last clone is dropped.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::rapidlogging::{Logger, Level, background::AsyncLogger};
+/// let logger: AsyncLogger = AsyncLogger::new(Logger::new(10, true, false, None));
+///
+/// let workers: Vec<_> = (0..4).map(|i| {
+///     let l: AsyncLogger = logger.clone();
+///     std::thread::spawn(move || { let _ = l.info(format_args!("Worker {} done.", i)); })
+/// }).collect();
+/// for w in workers {
+///     w.join().unwrap();
+/// }
+/// assert!(logger.flush().is_ok());
+/// ```
+#[derive(Clone)]
+pub struct AsyncLogger {
+    shared: Arc<Shared>,
+}
+
+impl AsyncLogger {
+    /// Moves `logger` onto a new writer thread.
+    /// # Arguments
+    /// * `logger` - The `Logger`. Its `min_level` and clock are taken over by the `AsyncLogger`, so records get the time they were logged at.
+    /// # Returns
+    /// A new `AsyncLogger`.
+    #[must_use]
+    pub fn new(mut logger: Logger) -> AsyncLogger {
+        let min_level:          Level                                = logger.min_level;
+        let clock:              Option<Arc<dyn Clock>>               = logger.clock.clone();
+        let (sender, receiver): (Sender<Command>, Receiver<Command>) = mpsc::channel();
+        // Filtering happens before sending, so the thread lets everything through.
+        logger.min_level = Level::Trace;
+        let thread: JoinHandle<()> = thread::spawn(move || run(logger, receiver));
+
+        AsyncLogger { shared: Arc::new(Shared { sender:    Some(sender),
+                                                thread:    Some(thread),
+                                                min_level: AtomicU8::new(min_level as u8),
+                                                clock }) }
+    }
+    /// Checks whether messages of a level get logged. See `Logger::is_enabled`.
+    /// # Arguments
+    /// * `level` - The level.
+    /// # Returns
+    /// A `bool`.
+    #[must_use]
+    pub fn is_enabled(&self, level: Level) -> bool {
+        level as u8 >= self.shared.min_level.load(Ordering::Relaxed)
+    }
+    /// Sets the minimum level for all clones of this `AsyncLogger`.
+    /// # Arguments
+    /// * `level` - The new minimum level.
+    pub fn set_min_level(&self, level: Level) {
+        self.shared.min_level.store(level as u8, Ordering::Relaxed);
+    }
+    /// Logs a message without a level, which is never filtered. See `Logger::log`.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if the message was sent, otherwise the error message as a `String`.
+    pub fn log(&self, prefixes: Option<Vec<&str>>, msg: &str) -> Result<(), String> {
+        self.send_line(None, prefixes, &msg, &[])
+    }
+    /// Logs a message with a level. See `Logger::log_at`.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if the message was sent or filtered out, otherwise the error message as a `String`.
+    pub fn log_at<D: fmt::Display>(&self, level: Level, prefixes: Option<Vec<&str>>, msg: D) -> Result<(), String> {
+        if !self.is_enabled(level)
+        { return Ok(()); }
+        self.send_line(Some(level), prefixes, &msg, &[])
+    }
+    /// Logs a message with a level and key/value fields. See `Logger::log_with`.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if the message was sent or filtered out, otherwise the error message as a `String`.
+    pub fn log_with<D: fmt::Display>(&self, level: Level, msg: D, fields: &[(&str, Value)]) -> Result<(), String> {
+        if !self.is_enabled(level)
+        { return Ok(()); }
+        self.send_line(Some(level), None, &msg, fields)
+    }
+    /// Logs a message with `Level::Trace`. See `log_at`.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if the message was sent or filtered out, otherwise the error message as a `String`.
+    pub fn trace<D: fmt::Display>(&self, msg: D) -> Result<(), String> {
+        self.log_at(Level::Trace, None, msg)
+    }
+    /// Logs a message with `Level::Debug`. See `log_at`.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if the message was sent or filtered out, otherwise the error message as a `String`.
+    pub fn debug<D: fmt::Display>(&self, msg: D) -> Result<(), String> {
+        self.log_at(Level::Debug, None, msg)
+    }
+    /// Logs a message with `Level::Info`. See `log_at`.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if the message was sent or filtered out, otherwise the error message as a `String`.
+    pub fn info<D: fmt::Display>(&self, msg: D) -> Result<(), String> {
+        self.log_at(Level::Info, None, msg)
+    }
+    /// Logs a message with `Level::Warn`. See `log_at`.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if the message was sent or filtered out, otherwise the error message as a `String`.
+    pub fn warn<D: fmt::Display>(&self, msg: D) -> Result<(), String> {
+        self.log_at(Level::Warn, None, msg)
+    }
+    /// Logs a message with `Level::Error`. See `log_at`.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if the message was sent or filtered out, otherwise the error message as a `String`.
+    pub fn error<D: fmt::Display>(&self, msg: D) -> Result<(), String> {
+        self.log_at(Level::Error, None, msg)
+    }
+    /// Waits until the writer thread has written everything sent so far, from any clone.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if it was successful, otherwise the first error the writer thread ran into since the last flush.
+    pub fn flush(&self) -> Result<(), String> {
+        let (sender, receiver): (Sender<Reply>, Receiver<Reply>) = mpsc::channel();
+        self.send(Command::Flush(sender))?;
+        receiver.recv().map_err(|_| "The writer thread has stopped.".to_string())?
+    }
+
+    fn send_line(&self, level: Option<Level>, prefixes: Option<Vec<&str>>, msg: &dyn fmt::Display, fields: &[(&str, Value)]) -> Result<(), String> {
+        self.send(Command::Line { level,
+                                  prefixes: prefixes.map(|v| v.into_iter().map(str::to_string).collect()),
+                                  msg:      msg.to_string(),
+                                  keys:     fields.iter().map(|(k, _)| k.to_string()).collect(),
+                                  values:   fields.iter().map(|(_, v)| v.clone()).collect(),
+                                  time:     self.shared.clock.as_ref().map_or_else(Utc::now, |c| c.now()) })
+    }
+
+    fn send(&self, cmd: Command) -> Result<(), String> {
+        self.shared.sender.as_ref()
+                          .unwrap()
+                          .send(cmd)
+                          .map_err(|_| "The writer thread has stopped.".to_string())
+    }
+}
+
+/// The loop of the writer thread. Ends when all senders are gone.
+fn run(mut logger: Logger, receiver: Receiver<Command>) {
+    let mut error: Option<String> = None;
+    for cmd in receiver {
+        match cmd {
+            Command::Line { level, prefixes, msg, keys, values, time } => {
+                let prefixes: Option<Vec<&str>>   = prefixes.as_ref().map(|v| v.iter().map(String::as_str).collect());
+                let fields:   Vec<(&str, Value)> = keys.iter().map(String::as_str).zip(values).collect();
+                if let Err(e) = logger.push_line(level, prefixes, &msg, &fields, time)
+                { error.get_or_insert(e); }
+            }
+            Command::Flush(reply) => {
+                let res: Reply = logger.flush();
+                let _ = reply.send(match error.take() {
+                    Some(e) => Err(e),
+                    None    => res,
+                });
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,134 @@
+//! The bridge between `Logger` and the `log` crate facade.
+//! Records keep their level and get their target, usually the module path, as a prefix.
+//! Buffering works as usual, so call `log::logger().flush()` before exiting to write out the rest of the buffer.
+use std::sync::{Mutex, MutexGuard, OnceLock, PoisonError};
+use super::{Level, Logger};
+
+impl From<log::Level> for Level {
+    fn from(level: log::Level) -> Level {
+        match level {
+            log::Level::Trace => Level::Trace,
+            log::Level::Debug => Level::Debug,
+            log::Level::Info  => Level::Info,
+            log::Level::Warn  => Level::Warn,
+            log::Level::Error => Level::Error,
+        }
+    }
+}
+
+impl From<Level> for log::Level {
+    fn from(level: Level) -> log::Level {
+        match level {
+            Level::Trace => log::Level::Trace,
+            Level::Debug => log::Level::Debug,
+            Level::Info  => log::Level::Info,
+            Level::Warn  => log::Level::Warn,
+            Level::Error => log::Level::Error,
+        }
+    }
+}
+
+/// A `Logger` which can be shared as the global `log::Log` implementation.
+pub struct LogFacade {
+    logger: Mutex<Logger>,
+}
+
+impl LogFacade {
+    /// Creates a new `LogFacade` around `logger`.
+    /// # Arguments
+    /// * `logger` - The `Logger` to receive the records.
+    /// # Returns
+    /// A new `LogFacade`.
+    #[must_use]
+    pub const fn new(logger: Logger) -> LogFacade {
+        LogFacade { logger: Mutex::new(logger) }
+    }
+    /// Sets the minimum level of the `Logger` and the maximum level of the `log` crate,
+    /// so the macros skip suppressed records without formatting them.
+    /// # Arguments
+    /// * `level` - The new minimum level.
+    pub fn set_min_level(&self, level: Level) {
+        self.lock().min_level = level;
+        log::set_max_level(log::Level::from(level).to_level_filter());
+    }
+    /// Locks the wrapped `Logger`, e.g. for changing its settings.
+    /// # Returns
+    /// A `MutexGuard<Logger>`.
+    pub fn lock(&self) -> MutexGuard<'_, Logger> {
+        // A panic while logging leaves the logger usable, so poisoning is ignored.
+        self.logger.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl log::Log for LogFacade {
+    fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+        self.lock().is_enabled(metadata.level().into())
+    }
+    fn log(&self, record: &log::Record<'_>) {
+        // `log::Log` has no way to report errors.
+        let _ = self.lock().log_at(record.level().into(), Some(vec![record.target()]), record.args());
+    }
+    fn flush(&self) {
+        let _ = self.lock().flush();
+    }
+}
+
+/// The facade set up by `install`.
+static INSTALLED: OnceLock<LogFacade> = OnceLock::new();
+
+/// The global logger registered by `install`. Forwards to `INSTALLED`, so a `Logger` only becomes static once registering succeeded.
+struct Installed;
+
+impl log::Log for Installed {
+    fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+        INSTALLED.get().is_some_and(|f| f.enabled(metadata))
+    }
+    fn log(&self, record: &log::Record<'_>) {
+        if let Some(f) = INSTALLED.get()
+        { f.log(record); }
+    }
+    fn flush(&self) {
+        if let Some(f) = INSTALLED.get()
+        { f.flush(); }
+    }
+}
+
+/// Installs `logger` as the global logger of the `log` crate. This can only be done once per program.
+/// If it fails, `logger` is dropped, which writes out its buffer.
+/// # Arguments
+/// * `logger` - The `Logger`. Its `min_level` also becomes the maximum level of the `log` crate.
+/// # Returns
+/// A `Result<&'static LogFacade, String>` with the installed logger, or the error message if a global logger was already set.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::rapidlogging::{Logger, Level, facade};
+/// let mut logger: Logger = Logger::new(10, true, false, None);
+/// logger.min_level = Level::Info;
+/// let installed = facade::install(logger).unwrap();
+///
+/// log::info!("Captured.");
+/// log::debug!("Dropped.");
+///
+/// let path: String     = std::env::temp_dir().join("lib_rapid_facade_example.log").to_string_lossy().into_owned();
+/// # let _ = std::fs::remove_file(&path);
+/// let mut late: Logger = Logger::new(10, false, true, Some(path.clone()));
+/// let _ = late.info("Written although the logger is rejected.");
+/// assert!(facade::install(late).is_err());
+/// assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 1);
+/// # std::fs::remove_file(&path).unwrap();
+///
+/// installed.set_min_level(Level::Debug);
+/// log::debug!("Captured as well.");
+/// log::logger().flush();
+/// ```
+/// Prints something like `[2022-01-01T12:00:00Z][INFO][rust_out] Captured.`.
+pub fn install(logger: Logger) -> Result<&'static LogFacade, String> {
+    let level: log::LevelFilter = log::Level::from(logger.min_level).to_level_filter();
+    if let Err(e) = log::set_logger(&Installed)
+    { return Err(format!("Problem installing the logger: {}", e)); }
+
+    // Registering succeeds only once, so `INSTALLED` is still empty. No records arrive before `set_max_level`.
+    let facade: &'static LogFacade = INSTALLED.get_or_init(|| LogFacade::new(logger));
+    log::set_max_level(level);
+    Ok(facade)
+}
\ No newline at end of file
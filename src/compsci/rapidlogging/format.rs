@@ -0,0 +1,272 @@
+//! Output formats for log records and the key/value fields which can be attached to them.
+//! # Format
+//! * `Text` - `[timestamp][LEVEL][prefix] message key=value`, the classic format of `Logger`.
+//! * `Logfmt` - `ts=timestamp level=LEVEL prefixes=a,b msg="message" key=value`.
+//! * `JsonLines` - one JSON object per line: `{"ts":"timestamp","level":"LEVEL","prefixes":["a","b"],"msg":"message","key":value}`.
+//!
+//! Fields follow the built-in keys in the order they were given. Records without a level or prefixes leave those keys out.
+use std::fmt::{self, Write};
+use super::Level;
+
+/// The output format of a `Logger`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Format {
+    /// Human-readable lines, with the fields as `key=value` pairs after the message.
+    #[default]
+    Text,
+    /// `key=value` pairs, as understood by many log shippers. Values are quoted where needed.
+    Logfmt,
+    /// One JSON object per line.
+    JsonLines,
+}
+
+/// The value of a field.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::rapidlogging::format::Value;
+/// assert_eq!(Value::from(200u16), Value::Int(200));
+/// assert_eq!(Value::from("GET").to_string(), "GET");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A string, quoted and escaped in the output.
+    Str(String),
+    /// An integer.
+    Int(i128),
+    /// A floating point number. `NaN` and the infinities become `null` in JSON.
+    Float(f64),
+    /// A boolean.
+    Bool(bool),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Str(s)   => write!(f, "{}", s),
+            Value::Int(i)   => write!(f, "{}", i),
+            Value::Float(x) => write!(f, "{}", x),
+            Value::Bool(b)  => write!(f, "{}", b),
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Value {
+        Value::Str(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Value {
+        Value::Str(s)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Value {
+        Value::Bool(b)
+    }
+}
+
+macro_rules! impl_value_from {
+    ($variant: ident, $conv: ty; $($t: ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(v: $t) -> Value {
+                    Value::$variant(v as $conv)
+                }
+            }
+        )*
+    };
+}
+
+impl_value_from!(Int, i128; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+impl_value_from!(Float, f64; f32, f64);
+
+/// A single log record, before it is formatted.
+#[derive(Debug, Clone, Copy)]
+pub struct Record<'a> {
+    /// The formatted timestamp.
+    pub timestamp: &'a str,
+    /// The level, if any.
+    pub level:     Option<Level>,
+    /// The prefixes, e.g. the module or subsystem.
+    pub prefixes:  &'a [&'a str],
+    /// The message.
+    pub message:   &'a str,
+    /// The key/value fields.
+    pub fields:    &'a [(&'a str, Value)],
+}
+
+/// Turns records into lines of text. Implemented by `Format` and by closures taking a `&Record` and returning a `String`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::rapidlogging::format::{Formatter, Record};
+/// let short = |r: &Record<'_>| format!("{}\n", r.message);
+/// let rec = Record { timestamp: "2022-01-01T12:00:00Z", level: None, prefixes: &[], message: "Done.", fields: &[] };
+///
+/// assert_eq!(short.format(&rec), "Done.\n");
+/// ```
+pub trait Formatter: Send {
+    /// Formats a record into a line, including the trailing line break.
+    /// # Arguments
+    /// * `record` - The record.
+    /// # Returns
+    /// A `String`.
+    fn format(&self, record: &Record<'_>) -> String;
+}
+
+impl Formatter for Format {
+    fn format(&self, record: &Record<'_>) -> String {
+        Format::format(self, record)
+    }
+}
+
+impl<F: Fn(&Record<'_>) -> String + Send> Formatter for F {
+    fn format(&self, record: &Record<'_>) -> String {
+        self(record)
+    }
+}
+
+impl Format {
+    /// Formats a record into a line, including the trailing line break.
+    /// # Arguments
+    /// * `record` - The record.
+    /// # Returns
+    /// A `String`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::rapidlogging::{Level, format::{Format, Record}};
+    /// let rec = Record { timestamp: "2022-01-01T12:00:00Z",
+    ///                    level:     Some(Level::Warn),
+    ///                    prefixes:  &["Net"],
+    ///                    message:   "Slow \"GET\".",
+    ///                    fields:    &[("ms", 950.into()), ("path", "/a b".into())] };
+    ///
+    /// assert_eq!(Format::Text.format(&rec),
+    ///            "[2022-01-01T12:00:00Z][WARN][Net] Slow \"GET\". ms=950 path=\"/a b\"\n");
+    /// assert_eq!(Format::Logfmt.format(&rec),
+    ///            "ts=2022-01-01T12:00:00Z level=WARN prefixes=Net msg=\"Slow \\\"GET\\\".\" ms=950 path=\"/a b\"\n");
+    /// assert_eq!(Format::JsonLines.format(&rec),
+    ///            "{\"ts\":\"2022-01-01T12:00:00Z\",\"level\":\"WARN\",\"prefixes\":[\"Net\"],\"msg\":\"Slow \\\"GET\\\".\",\"ms\":950,\"path\":\"/a b\"}\n");
+    /// ```
+    #[must_use]
+    pub fn format(&self, record: &Record<'_>) -> String {
+        let mut out: String = String::with_capacity(64 + record.message.len());
+        match self {
+            Format::Text      => format_text(&mut out, record),
+            Format::Logfmt    => format_logfmt(&mut out, record),
+            Format::JsonLines => format_json(&mut out, record),
+        }
+        out.push('\n');
+        out
+    }
+}
+
+fn format_text(out: &mut String, record: &Record<'_>) {
+    let _ = write!(out, "[{}]", record.timestamp);
+    if let Some(l) = record.level
+    { let _ = write!(out, "[{}]", l); }
+    for p in record.prefixes
+    { let _ = write!(out, "[{}]", p); }
+    out.push(' ');
+    out.push_str(record.message);
+    for (k, v) in record.fields {
+        out.push(' ');
+        push_logfmt_pair(out, k, v);
+    }
+}
+
+fn format_logfmt(out: &mut String, record: &Record<'_>) {
+    out.push_str("ts=");
+    push_logfmt_str(out, record.timestamp);
+    if let Some(l) = record.level
+    { let _ = write!(out, " level={}", l); }
+    if !record.prefixes.is_empty() {
+        out.push_str(" prefixes=");
+        push_logfmt_str(out, &record.prefixes.join(","));
+    }
+    out.push_str(" msg=");
+    push_logfmt_str(out, record.message);
+    for (k, v) in record.fields {
+        out.push(' ');
+        push_logfmt_pair(out, k, v);
+    }
+}
+
+fn format_json(out: &mut String, record: &Record<'_>) {
+    out.push_str("{\"ts\":");
+    push_json_str(out, record.timestamp);
+    if let Some(l) = record.level
+    { let _ = write!(out, ",\"level\":\"{}\"", l); }
+    if !record.prefixes.is_empty() {
+        out.push_str(",\"prefixes\":[");
+        for (i, p) in record.prefixes.iter().enumerate() {
+            if i > 0
+            { out.push(','); }
+            push_json_str(out, p);
+        }
+        out.push(']');
+    }
+    out.push_str(",\"msg\":");
+    push_json_str(out, record.message);
+    for (k, v) in record.fields {
+        out.push(',');
+        push_json_str(out, k);
+        out.push(':');
+        match v {
+            Value::Str(s)                     => push_json_str(out, s),
+            Value::Float(x) if !x.is_finite() => out.push_str("null"),
+            _                                 => { let _ = write!(out, "{}", v); }
+        }
+    }
+    out.push('}');
+}
+
+/// Appends `key=value`. Characters which would break the pair are replaced by `_` in the key.
+fn push_logfmt_pair(out: &mut String, key: &str, value: &Value) {
+    if key.is_empty()
+    { out.push('_'); }
+    for c in key.chars()
+    { out.push(if c == '=' || c == '"' || c <= ' ' { '_' } else { c }); }
+    out.push('=');
+    match value {
+        Value::Str(s) => push_logfmt_str(out, s),
+        _             => { let _ = write!(out, "{}", value); }
+    }
+}
+
+/// Appends a logfmt value, quoted and escaped if it is empty or contains spaces, `=`, `"`, `\` or control characters.
+fn push_logfmt_str(out: &mut String, s: &str) {
+    let needs_quotes: bool = s.is_empty()
+                             || s.chars().any(|c| c == ' ' || c == '=' || c == '"' || c == '\\' || c.is_control());
+    if !needs_quotes {
+        out.push_str(s);
+        return;
+    }
+    out.push('"');
+    push_escaped(out, s);
+    out.push('"');
+}
+
+/// Appends a JSON string literal.
+fn push_json_str(out: &mut String, s: &str) {
+    out.push('"');
+    push_escaped(out, s);
+    out.push('"');
+}
+
+/// Escapes quotes, backslashes and control characters the way JSON does, which logfmt parsers understand as well.
+fn push_escaped(out: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            '"'                 => out.push_str("\\\""),
+            '\\'                => out.push_str("\\\\"),
+            '\n'                => out.push_str("\\n"),
+            '\r'                => out.push_str("\\r"),
+            '\t'                => out.push_str("\\t"),
+            c if c.is_control() => { let _ = write!(out, "\\u{:04x}", c as u32); }
+            c                   => out.push(c),
+        }
+    }
+}
\ No newline at end of file
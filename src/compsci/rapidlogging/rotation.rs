@@ -0,0 +1,183 @@
+//! Rotation of log files, so a long-running program doesn't fill the disk with a single log.
+//! # Format
+//! Rotated files are numbered, `1` being the newest: `log.txt` becomes `log.txt.1`, `log.txt.1` becomes `log.txt.2` and so on.
+//! Compressed files get the extension `.hls` on top, e.g. `log.txt.1.hls`, and hold a stream of `compression::huffman::stream`.
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, ErrorKind};
+use std::path::Path;
+use chrono::{DateTime, Utc};
+use crate::compsci::compression::huffman::stream::HuffmanWriter;
+
+/// The extension appended to compressed rotated files.
+pub const COMPRESSED_EXTENSION: &str = "hls";
+
+/// The time boundaries at which a log file gets rotated. Boundaries are in UTC, whatever zone the timestamps of `Logger` are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interval {
+    /// Rotates at the start of every hour.
+    Hourly,
+    /// Rotates at midnight.
+    Daily,
+}
+
+impl Interval {
+    /// Get the number of the period `time` falls into, counted from the Unix epoch.
+    fn period_of(&self, time: DateTime<Utc>) -> i64 {
+        match self {
+            Interval::Hourly => time.timestamp().div_euclid(3600),
+            Interval::Daily  => time.timestamp().div_euclid(86400),
+        }
+    }
+}
+
+/// When and how the log file of a `Logger` is rotated.
+/// Rotation is checked whenever the buffer gets written, so a full buffer always lands in one file.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::rapidlogging::{Logger, rotation::Rotation};
+/// use std::path::Path;
+/// let path: String = std::env::temp_dir().join("lib_rapid_rotation_example.log").to_string_lossy().into_owned();
+/// let mut l: Logger = Logger::new(1, false, true, Some(path.clone()));
+/// l.rotation = Some(Rotation::new(Some(100), None, 2, false));
+///
+/// for i in 0..10 {
+///     l.info(format_args!("Step {} of the simulation.", i)).unwrap();
+/// }
+/// assert!(Path::new(&format!("{}.1", path)).exists());
+/// assert!(Path::new(&format!("{}.2", path)).exists());
+/// assert!(!Path::new(&format!("{}.3", path)).exists());
+/// # for p in [path.clone(), format!("{}.1", path), format!("{}.2", path)].iter() { std::fs::remove_file(p).unwrap(); }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation {
+    /// Rotates before the file would grow beyond this many bytes. A single buffer which is bigger still gets written in one piece.
+    pub max_size: Option<u64>,
+    /// Rotates when a time boundary has been crossed since the file was last written to.
+    pub interval: Option<Interval>,
+    /// The number of rotated files to keep. `0` deletes the file instead of rotating it.
+    pub keep:     usize,
+    /// Determines whether rotated files get Huffman-compressed.
+    pub compress: bool,
+}
+
+impl Rotation {
+    /// Creates a new `Rotation`.
+    /// # Arguments
+    /// * `max_size` - The optional maximum size of the file in bytes.
+    /// * `interval` - The optional time boundary.
+    /// * `keep` - The number of rotated files to keep.
+    /// * `compress` - Whether to compress rotated files.
+    /// # Returns
+    /// A new `Rotation`.
+    /// # Panics
+    /// Panics if neither `max_size` nor `interval` is given, or if `max_size` is `0`.
+    #[must_use]
+    pub const fn new(max_size: Option<u64>, interval: Option<Interval>, keep: usize, compress: bool) -> Rotation {
+        if max_size.is_none() && interval.is_none()
+        { panic!("A rotation needs a maximum size, an interval or both."); }
+        if let Some(0) = max_size
+        { panic!("The maximum size must be positive."); }
+
+        Rotation { max_size, interval, keep, compress }
+    }
+    /// Creates a new `Rotation` with default values: daily or at 10 MiB, keeping 7 compressed files.
+    /// # Returns
+    /// A new `Rotation`.
+    #[must_use]
+    pub const fn new_default() -> Rotation {
+        Rotation::new(Some(10 << 20), Some(Interval::Daily), 7, true)
+    }
+    /// Rotates `path` if `incoming` more bytes would exceed `max_size` or if `now` lies in another interval than `period`.
+    /// `period` remembers the interval of the last write; if unknown, the modification time of the file is used.
+    pub(super) fn apply(&self, path: &str, incoming: u64, period: &mut Option<i64>, now: DateTime<Utc>) -> io::Result<()> {
+        let (size, modified) = match fs::metadata(path) {
+            Ok(m)                                     => (m.len(), m.modified().ok()),
+            Err(e) if e.kind() == ErrorKind::NotFound => (0, None),
+            Err(e)                                    => { return Err(e); }
+        };
+        let current: Option<i64> = self.interval.map(|i| i.period_of(now));
+        if period.is_none()
+        { *period = self.interval.zip(modified).map(|(i, m)| i.period_of(m.into())); }
+
+        let due_time: bool = current.is_some() && period.is_some() && current != *period;
+        let due_size: bool = self.max_size.is_some_and(|m| size + incoming > m);
+        if size > 0 && (due_time || due_size)
+        { rotate_file(path, self.keep, self.compress)?; }
+        *period = current;
+        Ok(())
+    }
+}
+
+/// Rotates a log file right away: shifts the numbers of the rotated files up by one, drops the oldest and moves `path` to number `1`.
+/// # Arguments
+/// * `path` - The path of the log file.
+/// * `keep` - The number of rotated files to keep. `0` simply deletes `path`.
+/// * `compress` - Whether to compress the newly rotated file.
+/// # Returns
+/// A `std::io::Result<()>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::rapidlogging::rotation::rotate_file;
+/// use lib_rapid::compsci::compression::huffman::stream::HuffmanReader;
+/// use std::{fs::File, io::Read};
+/// let path: String = std::env::temp_dir().join("lib_rapid_rotate_file_example.log").to_string_lossy().into_owned();
+/// std::fs::write(&path, "Finished.\n").unwrap();
+/// rotate_file(&path, 3, true).unwrap();
+///
+/// let mut res = String::new();
+/// HuffmanReader::new(File::open(format!("{}.1.hls", path)).unwrap()).read_to_string(&mut res).unwrap();
+/// assert_eq!(res, "Finished.\n");
+/// # std::fs::remove_file(format!("{}.1.hls", path)).unwrap();
+/// ```
+pub fn rotate_file(path: &str, keep: usize, compress: bool) -> io::Result<()> {
+    if keep == 0
+    { return ignore_missing(fs::remove_file(path)); }
+
+    for c in [false, true].iter() {
+        ignore_missing(fs::remove_file(rotated_name(path, keep, *c)))?;
+        for i in (1..keep).rev()
+        { ignore_missing(fs::rename(rotated_name(path, i, *c), rotated_name(path, i + 1, *c)))?; }
+    }
+
+    let first: String = rotated_name(path, 1, false);
+    fs::rename(path, &first)?;
+    if compress
+    { compress_file(&first)?; }
+    Ok(())
+}
+
+/// Get the name of rotated file number `i`.
+fn rotated_name(path: &str, i: usize, compressed: bool) -> String {
+    if compressed
+    { format!("{}.{}.{}", path, i, COMPRESSED_EXTENSION) }
+    else
+    { format!("{}.{}", path, i) }
+}
+
+/// Replaces `path` by its compressed version. On failure, `path` is kept and the partial output removed.
+fn compress_file(path: &str) -> io::Result<()> {
+    let target: String = format!("{}.{}", path, COMPRESSED_EXTENSION);
+    match write_compressed(path, &target) {
+        Ok(()) => fs::remove_file(path),
+        Err(e) => {
+            if Path::new(&target).exists()
+            { let _ = fs::remove_file(&target); }
+            Err(e)
+        }
+    }
+}
+
+fn write_compressed(path: &str, target: &str) -> io::Result<()> {
+    let mut input:  BufReader<File>                = BufReader::new(File::open(path)?);
+    let mut writer: HuffmanWriter<BufWriter<File>> = HuffmanWriter::new(BufWriter::new(File::create(target)?));
+    io::copy(&mut input, &mut writer)?;
+    writer.finish()?.into_inner()?.sync_all()
+}
+
+/// Treats a missing file as success.
+fn ignore_missing(res: io::Result<()>) -> io::Result<()> {
+    match res {
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+        _                                         => res,
+    }
+}
\ No newline at end of file
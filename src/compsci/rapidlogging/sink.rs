@@ -0,0 +1,291 @@
+//! Destinations for log records. A `Logger` hands every record to each of its sinks, which filter and format it on their own.
+//! Buffering sinks are flushed whenever the `Logger` writes out its buffer, with the current time of the `Logger`'s clock.
+use std::collections::VecDeque;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use chrono::{DateTime, Utc};
+use super::{Level, format::{Formatter, Record}, rotation::Rotation};
+
+/// A destination for log records.
+pub trait Sink: Send {
+    /// Get the minimum level of the records this sink accepts. Records without a level are always accepted.
+    /// # Returns
+    /// A `Level`. `Level::Trace` unless overridden.
+    fn min_level(&self) -> Level {
+        Level::Trace
+    }
+    /// Takes a record. Sinks may hold it back until `flush` is called.
+    /// # Arguments
+    /// * `record` - The record.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if it was successful, otherwise the error message as a `String`.
+    fn log(&mut self, record: &Record<'_>) -> Result<(), String>;
+    /// Writes out everything held back.
+    /// # Arguments
+    /// * `now` - The current time, e.g. of the clock of the `Logger`. Time-based rotation goes by it.
+    /// # Returns
+    /// A `Result<(), String>`. `()` if it was successful, otherwise the error message as a `String`.
+    fn flush(&mut self, _now: DateTime<Utc>) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Writes every record to the standard error stream right away.
+pub struct StderrSink {
+    min_level: Level,
+    formatter: Box<dyn Formatter>,
+}
+
+impl StderrSink {
+    /// Creates a new `StderrSink`.
+    /// # Arguments
+    /// * `min_level` - The minimum level of the records to write.
+    /// * `formatter` - The formatter, e.g. a `Format`.
+    /// # Returns
+    /// A new `StderrSink`.
+    #[must_use]
+    pub fn new<F: Formatter + 'static>(min_level: Level, formatter: F) -> StderrSink {
+        StderrSink { min_level, formatter: Box::new(formatter) }
+    }
+}
+
+impl Sink for StderrSink {
+    fn min_level(&self) -> Level {
+        self.min_level
+    }
+    fn log(&mut self, record: &Record<'_>) -> Result<(), String> {
+        io::stderr().write_all(self.formatter.format(record).as_bytes())
+                    .map_err(|e| format!("Problem writing to stderr: {:?}", e))
+    }
+}
+
+/// Appends records to a file, optionally rotating it. Records are held back until the sink is flushed or dropped.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::rapidlogging::{Logger, Level, format::Format, sink::{FileSink, StderrSink}};
+/// let path: String = std::env::temp_dir().join("lib_rapid_file_sink_example.jsonl").to_string_lossy().into_owned();
+/// let mut l: Logger = Logger::new(10, false, false, None);
+/// l.add_sink(FileSink::new(&path, Level::Debug, Format::JsonLines));
+/// l.add_sink(StderrSink::new(Level::Error, Format::Text));
+///
+/// let _ = l.debug("Only in the file.");
+/// let _ = l.error("In the file and on stderr.");
+/// l.flush().unwrap();
+/// assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct FileSink {
+    path:      String,
+    min_level: Level,
+    formatter: Box<dyn Formatter>,
+    buffer:    String,
+    rotation:  Option<Rotation>,
+    period:    Option<i64>,
+}
+
+impl FileSink {
+    /// Creates a new `FileSink`. The file is created on the first flush if it doesn't exist.
+    /// # Arguments
+    /// * `path` - The path of the file.
+    /// * `min_level` - The minimum level of the records to write.
+    /// * `formatter` - The formatter, e.g. a `Format`.
+    /// # Returns
+    /// A new `FileSink`.
+    #[must_use]
+    pub fn new<F: Formatter + 'static>(path: &str, min_level: Level, formatter: F) -> FileSink {
+        FileSink { path:      path.to_string(),
+                   min_level,
+                   formatter: Box::new(formatter),
+                   buffer:    String::new(),
+                   rotation:  None,
+                   period:    None }
+    }
+    /// Creates a new `FileSink` which rotates its file.
+    /// # Arguments
+    /// * `path` - The path of the file.
+    /// * `min_level` - The minimum level of the records to write.
+    /// * `formatter` - The formatter, e.g. a `Format`.
+    /// * `rotation` - When and how to rotate the file.
+    /// # Returns
+    /// A new `FileSink`.
+    /// # Examples
+    /// ```
+    /// use lib_rapid::compsci::rapidlogging::{Logger, Level, format::Format, rotation::{Rotation, Interval}, sink::FileSink, timestamp::ManualClock};
+    /// use chrono::{Duration, TimeZone, Utc};
+    /// let path:  String      = std::env::temp_dir().join("lib_rapid_file_sink_rotation_example.log").to_string_lossy().into_owned();
+    /// let clock: ManualClock = ManualClock::new(Utc.with_ymd_and_hms(2022, 1, 1, 12, 0, 0).unwrap());
+    /// let mut l: Logger      = Logger::new(1, false, false, None);
+    /// l.set_clock(clock.clone());
+    /// l.add_sink(FileSink::with_rotation(&path, Level::Trace, Format::Text, Rotation::new(None, Some(Interval::Daily), 1, false)));
+    ///
+    /// let _ = l.info("Saturday.");
+    /// clock.advance(Duration::days(1));
+    /// let _ = l.info("Sunday."); // The clock of the logger crossed midnight, so the file gets rotated.
+    /// assert!(std::fs::read_to_string(format!("{}.1", path)).unwrap().ends_with("Saturday.\n"));
+    /// assert!(std::fs::read_to_string(&path).unwrap().ends_with("Sunday.\n"));
+    /// # for p in [path.clone(), format!("{}.1", path)].iter() { std::fs::remove_file(p).unwrap(); }
+    /// ```
+    #[must_use]
+    pub fn with_rotation<F: Formatter + 'static>(path: &str, min_level: Level, formatter: F, rotation: Rotation) -> FileSink {
+        let mut res: FileSink = FileSink::new(path, min_level, formatter);
+        res.rotation = Some(rotation);
+        res
+    }
+}
+
+impl Sink for FileSink {
+    fn min_level(&self) -> Level {
+        self.min_level
+    }
+    fn log(&mut self, record: &Record<'_>) -> Result<(), String> {
+        self.buffer.push_str(&self.formatter.format(record));
+        Ok(())
+    }
+    fn flush(&mut self, now: DateTime<Utc>) -> Result<(), String> {
+        if self.buffer.is_empty()
+        { return Ok(()); }
+        if let Some(r) = self.rotation {
+            r.apply(&self.path, self.buffer.len() as u64, &mut self.period, now)
+             .map_err(|e| format!("Problem rotating file: {:?}", e))?;
+        }
+
+        OpenOptions::new().create(true)
+                          .append(true)
+                          .open(&self.path)
+                          .and_then(|mut f| f.write_all(self.buffer.as_bytes()))
+                          .map_err(|e| format!("Problem writing to file: {:?}", e))?;
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+impl Drop for FileSink {
+    /// Flushes with the system time. Sinks of a `Logger` have already been flushed with its clock by then.
+    fn drop(&mut self) {
+        let _ = self.flush(Utc::now());
+    }
+}
+
+/// Writes every record to any `std::io::Write` right away, e.g. a socket or a `BufWriter`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::rapidlogging::{Level, format::{Format, Record}, sink::{Sink, WriterSink}};
+/// let mut sink = WriterSink::new(Vec::new(), Level::Trace, Format::Logfmt);
+/// let rec = Record { timestamp: "2022-01-01T12:00:00Z", level: Some(Level::Info), prefixes: &[], message: "Hi", fields: &[] };
+/// sink.log(&rec).unwrap();
+///
+/// assert_eq!(sink.get_ref(), b"ts=2022-01-01T12:00:00Z level=INFO msg=Hi\n");
+/// ```
+pub struct WriterSink<W: Write + Send> {
+    inner:     W,
+    min_level: Level,
+    formatter: Box<dyn Formatter>,
+}
+
+impl<W: Write + Send> WriterSink<W> {
+    /// Creates a new `WriterSink`.
+    /// # Arguments
+    /// * `inner` - The destination.
+    /// * `min_level` - The minimum level of the records to write.
+    /// * `formatter` - The formatter, e.g. a `Format`.
+    /// # Returns
+    /// A new `WriterSink`.
+    #[must_use]
+    pub fn new<F: Formatter + 'static>(inner: W, min_level: Level, formatter: F) -> WriterSink<W> {
+        WriterSink { inner, min_level, formatter: Box::new(formatter) }
+    }
+    /// Get a reference to the underlying writer.
+    /// # Returns
+    /// A `&W`.
+    #[must_use]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+}
+
+impl<W: Write + Send> Sink for WriterSink<W> {
+    fn min_level(&self) -> Level {
+        self.min_level
+    }
+    fn log(&mut self, record: &Record<'_>) -> Result<(), String> {
+        self.inner.write_all(self.formatter.format(record).as_bytes())
+                  .map_err(|e| format!("Problem writing: {:?}", e))
+    }
+    fn flush(&mut self, _now: DateTime<Utc>) -> Result<(), String> {
+        self.inner.flush().map_err(|e| format!("Problem flushing: {:?}", e))
+    }
+}
+
+/// Keeps the most recent lines in memory, e.g. for asserting on logs in tests.
+/// Clones share the same lines, so keep a clone to look at the lines after adding the sink to a `Logger`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::rapidlogging::{Logger, Level, format::Record, sink::RingBufferSink};
+/// let ring: RingBufferSink = RingBufferSink::new(2, Level::Info, |r: &Record<'_>| format!("{}\n", r.message));
+/// let mut l: Logger = Logger::new(10, false, false, None);
+/// l.add_sink(ring.clone());
+///
+/// let _ = l.debug("Filtered out.");
+/// for msg in vec!["One.", "Two.", "Three."] {
+///     let _ = l.info(msg);
+/// }
+/// assert_eq!(ring.lines(), vec!["Two.", "Three."]);
+/// ```
+#[derive(Clone)]
+pub struct RingBufferSink {
+    lines:     Arc<Mutex<VecDeque<String>>>,
+    capacity:  usize,
+    min_level: Level,
+    formatter: Arc<dyn Formatter + Sync>,
+}
+
+impl RingBufferSink {
+    /// Creates a new `RingBufferSink`.
+    /// # Arguments
+    /// * `capacity` - The number of lines to keep.
+    /// * `min_level` - The minimum level of the records to keep.
+    /// * `formatter` - The formatter, e.g. a `Format`.
+    /// # Returns
+    /// A new `RingBufferSink`.
+    /// # Panics
+    /// Panics if `capacity` is `0`.
+    #[must_use]
+    pub fn new<F: Formatter + Sync + 'static>(capacity: usize, min_level: Level, formatter: F) -> RingBufferSink {
+        if capacity == 0
+        { panic!("The capacity must be positive."); }
+
+        RingBufferSink { lines:     Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+                         capacity,
+                         min_level,
+                         formatter: Arc::new(formatter) }
+    }
+    /// Get the kept lines without their line breaks, from the oldest to the newest.
+    /// # Returns
+    /// A `Vec<String>`.
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        self.lines.lock().unwrap_or_else(PoisonError::into_inner).iter().cloned().collect()
+    }
+    /// Removes all kept lines.
+    pub fn clear(&self) {
+        self.lines.lock().unwrap_or_else(PoisonError::into_inner).clear();
+    }
+}
+
+impl Sink for RingBufferSink {
+    fn min_level(&self) -> Level {
+        self.min_level
+    }
+    fn log(&mut self, record: &Record<'_>) -> Result<(), String> {
+        let mut line: String = self.formatter.format(record);
+        if line.ends_with('\n')
+        { line.pop(); }
+
+        let mut lines: MutexGuard<'_, VecDeque<String>> = self.lines.lock().unwrap_or_else(PoisonError::into_inner);
+        if lines.len() == self.capacity
+        { lines.pop_front(); }
+        lines.push_back(line);
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,190 @@
+//! Timestamps of log records: where the time comes from, and how it is written.
+use std::fmt::Display;
+use std::sync::{Arc, Mutex, PoisonError};
+use chrono::{DateTime, Duration, FixedOffset, Local, SecondsFormat, TimeZone, Utc};
+use chrono::format::{Item, StrftimeItems};
+
+/// A source of the current time. Implemented by `SystemClock`, `ManualClock` and closures returning a `DateTime<Utc>`.
+pub trait Clock: Send + Sync {
+    /// Get the current time.
+    /// # Returns
+    /// A `DateTime<Utc>`.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The system clock, which is used unless a `Logger` gets another clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+impl<F: Fn() -> DateTime<Utc> + Send + Sync> Clock for F {
+    fn now(&self) -> DateTime<Utc> {
+        self()
+    }
+}
+
+/// A clock which only moves when told to, for deterministic timestamps in tests.
+/// Clones share the same time, so keep a clone to move the time after handing the clock to a `Logger`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::rapidlogging::{Logger, Level, format::Format, sink::RingBufferSink, timestamp::ManualClock};
+/// use chrono::{Duration, TimeZone, Utc, SecondsFormat};
+/// let clock: ManualClock    = ManualClock::new(Utc.with_ymd_and_hms(2022, 1, 1, 12, 0, 0).unwrap());
+/// let ring:  RingBufferSink = RingBufferSink::new(10, Level::Trace, Format::Text);
+/// let mut l: Logger         = Logger::new(1, false, false, None);
+/// l.set_clock(clock.clone());
+/// l.add_sink(ring.clone());
+/// l.timestamp.precision = SecondsFormat::Millis;
+///
+/// let _ = l.info("Start.");
+/// clock.advance(Duration::milliseconds(1500));
+/// let _ = l.info("Step.");
+/// assert_eq!(ring.lines(), vec!["[2022-01-01T12:00:00.000Z][INFO] Start.",
+///                               "[2022-01-01T12:00:01.500Z][INFO] Step."]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    time: Arc<Mutex<DateTime<Utc>>>,
+}
+
+impl ManualClock {
+    /// Creates a new `ManualClock`.
+    /// # Arguments
+    /// * `start` - The time to start at.
+    /// # Returns
+    /// A new `ManualClock`.
+    #[must_use]
+    pub fn new(start: DateTime<Utc>) -> ManualClock {
+        ManualClock { time: Arc::new(Mutex::new(start)) }
+    }
+    /// Sets the time.
+    /// # Arguments
+    /// * `time` - The new time.
+    pub fn set(&self, time: DateTime<Utc>) {
+        *self.time.lock().unwrap_or_else(PoisonError::into_inner) = time;
+    }
+    /// Moves the time forward, or backward for a negative `by`.
+    /// # Arguments
+    /// * `by` - The duration to move by.
+    pub fn advance(&self, by: Duration) {
+        *self.time.lock().unwrap_or_else(PoisonError::into_inner) += by;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.time.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The time zone timestamps are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Zone {
+    /// Coordinated Universal Time, written with a `Z`.
+    Utc,
+    /// The local time zone of the system.
+    Local,
+    /// A fixed offset from UTC, e.g. `FixedOffset::east_opt(3600).unwrap()` for UTC+01:00.
+    Fixed(FixedOffset),
+}
+
+/// How timestamps are written.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::rapidlogging::timestamp::{Timestamp, Zone};
+/// use chrono::{FixedOffset, SecondsFormat, TimeZone, Utc};
+/// let time = Utc.with_ymd_and_hms(2022, 1, 1, 23, 30, 0).unwrap();
+/// let cet: Zone = Zone::Fixed(FixedOffset::east_opt(3600).unwrap());
+///
+/// assert_eq!(Timestamp::new_default().format(time), "2022-01-01T23:30:00Z");
+/// assert_eq!(Timestamp::new(SecondsFormat::Micros, cet, None).format(time), "2022-01-02T00:30:00.000000+01:00");
+/// assert_eq!(Timestamp::new(SecondsFormat::Secs, cet, Some("%d.%m.%Y %H:%M")).format(time), "02.01.2022 00:30");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Timestamp {
+    /// The digits of the seconds in RFC 3339 timestamps.
+    pub precision: SecondsFormat,
+    /// The time zone.
+    pub zone:      Zone,
+        pattern:   Option<String>,
+}
+
+impl Timestamp {
+    /// Creates a new `Timestamp`.
+    /// # Arguments
+    /// * `precision` - The digits of the seconds.
+    /// * `zone` - The time zone.
+    /// * `pattern` - An optional strftime pattern. If given, `precision` is ignored.
+    /// # Returns
+    /// A new `Timestamp`.
+    /// # Panics
+    /// Panics if `pattern` is not a valid strftime pattern.
+    #[must_use]
+    pub fn new(precision: SecondsFormat, zone: Zone, pattern: Option<&str>) -> Timestamp {
+        let mut res: Timestamp = Timestamp { precision, zone, pattern: None };
+        if let Some(p) = pattern
+        { res.set_pattern(p); }
+        res
+    }
+    /// Creates a new `Timestamp` with default values: RFC 3339 in UTC with whole seconds.
+    /// # Returns
+    /// A new `Timestamp`.
+    #[must_use]
+    pub const fn new_default() -> Timestamp {
+        Timestamp { precision: SecondsFormat::Secs, zone: Zone::Utc, pattern: None }
+    }
+    /// Get the strftime pattern.
+    /// # Returns
+    /// An `Option<&str>`.
+    #[must_use]
+    pub fn pattern(&self) -> Option<&str> {
+        self.pattern.as_deref()
+    }
+    /// Sets the strftime pattern, as documented in `chrono::format::strftime`, which replaces RFC 3339 and `precision`.
+    /// # Arguments
+    /// * `pattern` - The pattern.
+    /// # Panics
+    /// Panics if `pattern` is not a valid strftime pattern.
+    pub fn set_pattern(&mut self, pattern: &str) {
+        // Formatting with an invalid pattern fails, so it is rejected here instead of on every record.
+        if StrftimeItems::new(pattern).any(|i| matches!(i, Item::Error))
+        { panic!("Invalid strftime pattern: {:?}", pattern); }
+        self.pattern = Some(pattern.to_string());
+    }
+    /// Removes the strftime pattern, going back to RFC 3339.
+    pub fn clear_pattern(&mut self) {
+        self.pattern = None;
+    }
+    /// Writes a point in time.
+    /// # Arguments
+    /// * `time` - The point in time.
+    /// # Returns
+    /// A `String`.
+    #[must_use]
+    pub fn format(&self, time: DateTime<Utc>) -> String {
+        match self.zone {
+            Zone::Utc      => self.format_in(time),
+            Zone::Local    => self.format_in(time.with_timezone(&Local)),
+            Zone::Fixed(o) => self.format_in(time.with_timezone(&o)),
+        }
+    }
+
+    fn format_in<Tz: TimeZone>(&self, time: DateTime<Tz>) -> String
+        where Tz::Offset: Display {
+        match &self.pattern {
+            Some(p) => time.format(p).to_string(),
+            None    => time.to_rfc3339_opts(self.precision, true),
+        }
+    }
+}
+
+impl Default for Timestamp {
+    fn default() -> Timestamp {
+        Timestamp::new_default()
+    }
+}
\ No newline at end of file
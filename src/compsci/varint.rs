@@ -0,0 +1,490 @@
+//! Variable-length integer encodings, which spend fewer bits on small values.
+//! LEB128 writes into byte buffers, Elias and Golomb codes write into `BitVec`s.
+//! All functions are generic over `u8` to `u128` and `i8` to `i128`.
+//!
+//! Decoders take the position to read from and advance it past the value, so several values can be read in a row.
+//! The position stays unchanged if decoding fails.
+use core::fmt;
+use std::convert::TryFrom;
+use bit_vec::BitVec;
+
+/// The errors that can occur while decoding a variable-length integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarintError {
+    /// The input ended in the middle of a value.
+    Truncated,
+    /// The value does not fit into the requested integer type.
+    Overflow,
+}
+
+impl fmt::Display for VarintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VarintError::Truncated => write!(f, "The input ends in the middle of a value."),
+            VarintError::Overflow  => write!(f, "The value does not fit into the integer type."),
+        }
+    }
+}
+
+impl std::error::Error for VarintError { }
+
+/// The unsigned integer types which can be encoded.
+pub trait Unsigned: Copy + Into<u128> + TryFrom<u128> { }
+/// The signed integer types which can be encoded.
+pub trait Signed: Copy + Into<i128> + TryFrom<i128> {
+    /// The unsigned type of the same width, which zigzag encoding maps to.
+    type Unsigned: Unsigned;
+}
+
+macro_rules! impl_varint {
+    ($($u: ty, $i: ty);*) => {
+        $(
+            impl Unsigned for $u { }
+            impl Signed for $i {
+                type Unsigned = $u;
+            }
+        )*
+    };
+}
+
+impl_varint!(u8, i8; u16, i16; u32, i32; u64, i64; u128, i128);
+
+/// Maps signed integers to unsigned ones so that small magnitudes stay small: `0, -1, 1, -2, 2` become `0, 1, 2, 3, 4`.
+/// # Arguments
+/// * `value` - The signed integer.
+/// # Returns
+/// The unsigned integer of the same width.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::varint::zigzag_encode;
+/// assert_eq!(zigzag_encode(-1i32), 1u32);
+/// assert_eq!(zigzag_encode(1i32), 2u32);
+/// assert_eq!(zigzag_encode(i8::MIN), u8::MAX);
+/// ```
+#[must_use]
+pub fn zigzag_encode<T: Signed>(value: T) -> T::Unsigned {
+    let v:      i128 = value.into();
+    // Independent of the width, so the result always fits into the unsigned type of the same width.
+    let zigzag: u128 = ((v << 1) ^ (v >> 127)) as u128;
+    T::Unsigned::try_from(zigzag).ok().unwrap()
+}
+
+/// Reverses `zigzag_encode`.
+/// # Arguments
+/// * `value` - The unsigned integer.
+/// # Returns
+/// The signed integer of the same width.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::varint::{zigzag_encode, zigzag_decode};
+/// assert_eq!(zigzag_decode::<i32>(3), -2);
+/// assert_eq!(zigzag_decode::<i64>(zigzag_encode(i64::MIN)), i64::MIN);
+/// ```
+#[must_use]
+pub fn zigzag_decode<T: Signed>(value: T::Unsigned) -> T {
+    let v:      u128 = value.into();
+    let signed: i128 = ((v >> 1) as i128) ^ -((v & 1) as i128);
+    T::try_from(signed).ok().unwrap()
+}
+
+/// Appends an unsigned integer as LEB128: 7 bits per byte, least significant first, the high bit marking that more bytes follow.
+/// # Arguments
+/// * `value` - The integer.
+/// * `out` - The buffer to append to.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::varint::{write_uleb128, read_uleb128};
+/// let mut buf: Vec<u8> = Vec::new();
+/// write_uleb128(624_485u32, &mut buf);
+/// write_uleb128(1u8, &mut buf);
+/// assert_eq!(buf, [0xE5, 0x8E, 0x26, 0x01]);
+///
+/// let mut pos = 0;
+/// assert_eq!(read_uleb128::<u32>(&buf, &mut pos), Ok(624_485));
+/// assert_eq!(read_uleb128::<u8>(&buf, &mut pos), Ok(1));
+/// assert_eq!(pos, 4);
+/// ```
+pub fn write_uleb128<T: Unsigned>(value: T, out: &mut Vec<u8>) {
+    let mut v: u128 = value.into();
+    loop {
+        let byte: u8 = (v & 0x7F) as u8;
+        v >>= 7;
+        if v == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Reads an unsigned LEB128 integer.
+/// # Arguments
+/// * `bytes` - The buffer.
+/// * `pos` - The position of the first byte. Advanced past the integer on success.
+/// # Returns
+/// A `Result<T, VarintError>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::varint::{read_uleb128, VarintError};
+/// assert_eq!(read_uleb128::<u8>(&[0x80, 0x02], &mut 0), Err(VarintError::Overflow));
+/// assert_eq!(read_uleb128::<u16>(&[0x80, 0x82], &mut 0), Err(VarintError::Truncated));
+/// ```
+pub fn read_uleb128<T: Unsigned>(bytes: &[u8], pos: &mut usize) -> Result<T, VarintError> {
+    let mut p:     usize = *pos;
+    let mut v:     u128  = 0;
+    let mut shift: u32   = 0;
+    loop {
+        let byte: u8 = *bytes.get(p).ok_or(VarintError::Truncated)?;
+        p += 1;
+        // The 19th byte holds the last two bits of a u128.
+        if shift == 126 && (byte & 0x80 != 0 || byte >> 2 != 0)
+        { return Err(VarintError::Overflow); }
+
+        v     |= ((byte & 0x7F) as u128) << shift;
+        shift += 7;
+        if byte & 0x80 == 0
+        { break; }
+    }
+
+    let res: T = T::try_from(v).map_err(|_| VarintError::Overflow)?;
+    *pos = p;
+    Ok(res)
+}
+
+/// Appends a signed integer as LEB128 in two's complement.
+/// # Arguments
+/// * `value` - The integer.
+/// * `out` - The buffer to append to.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::varint::{write_sleb128, read_sleb128};
+/// let mut buf: Vec<u8> = Vec::new();
+/// write_sleb128(-123_456i64, &mut buf);
+/// assert_eq!(buf, [0xC0, 0xBB, 0x78]);
+///
+/// assert_eq!(read_sleb128::<i64>(&buf, &mut 0), Ok(-123_456));
+/// ```
+pub fn write_sleb128<T: Signed>(value: T, out: &mut Vec<u8>) {
+    let mut v: i128 = value.into();
+    loop {
+        let byte: u8 = (v & 0x7F) as u8;
+        v >>= 7;
+        // Done once the remaining bits are all copies of the sign bit of `byte`.
+        if (v == 0 && byte & 0x40 == 0) || (v == -1 && byte & 0x40 != 0) {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Reads a signed LEB128 integer.
+/// # Arguments
+/// * `bytes` - The buffer.
+/// * `pos` - The position of the first byte. Advanced past the integer on success.
+/// # Returns
+/// A `Result<T, VarintError>`.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::varint::{read_sleb128, VarintError};
+/// assert_eq!(read_sleb128::<i8>(&[0x7F], &mut 0), Ok(-1));
+/// assert_eq!(read_sleb128::<i8>(&[0x80, 0x7F], &mut 0), Ok(-128));
+/// assert_eq!(read_sleb128::<i8>(&[0x80, 0x7E], &mut 0), Err(VarintError::Overflow));
+/// ```
+pub fn read_sleb128<T: Signed>(bytes: &[u8], pos: &mut usize) -> Result<T, VarintError> {
+    let mut p:     usize = *pos;
+    let mut v:     i128  = 0;
+    let mut shift: u32   = 0;
+    loop {
+        let byte: u8 = *bytes.get(p).ok_or(VarintError::Truncated)?;
+        p += 1;
+        // The 19th byte holds the last two bits of an i128, the rest has to repeat the sign bit.
+        if shift == 126 && (byte & 0x80 != 0 || !matches!((byte & 0x7F) >> 1, 0 | 0x3F))
+        { return Err(VarintError::Overflow); }
+
+        v     |= ((byte & 0x7F) as i128) << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            if shift < 128 && byte & 0x40 != 0
+            { v |= -1 << shift; }
+            break;
+        }
+    }
+
+    let res: T = T::try_from(v).map_err(|_| VarintError::Overflow)?;
+    *pos = p;
+    Ok(res)
+}
+
+/// Appends the Elias gamma code of a positive integer: `n` in binary, preceded by one `0` for every bit after the first.
+/// # Arguments
+/// * `value` - The integer.
+/// * `out` - The `BitVec` to append to.
+/// # Panics
+/// Panics if `value` is `0`, which Elias codes can't represent.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::varint::{write_gamma, read_gamma};
+/// use bit_vec::BitVec;
+/// let mut bits = BitVec::new();
+/// write_gamma(5u8, &mut bits);
+/// assert_eq!(bits, BitVec::from_fn(5, |i| [false, false, true, false, true][i]));
+///
+/// assert_eq!(read_gamma::<u8>(&bits, &mut 0), Ok(5));
+/// ```
+pub fn write_gamma<T: Unsigned>(value: T, out: &mut BitVec) {
+    let v: u128 = value.into();
+    if v == 0
+    { panic!("Elias codes can't represent 0."); }
+
+    let len: u32 = 128 - v.leading_zeros();
+    out.grow(len as usize - 1, false);
+    push_bits(out, v, len);
+}
+
+/// Reads an Elias gamma code.
+/// # Arguments
+/// * `bits` - The `BitVec`.
+/// * `pos` - The position of the first bit. Advanced past the code on success.
+/// # Returns
+/// A `Result<T, VarintError>`.
+pub fn read_gamma<T: Unsigned>(bits: &BitVec, pos: &mut usize) -> Result<T, VarintError> {
+    let mut p: usize = *pos;
+    let v:     u128  = gamma_value(bits, &mut p)?;
+    let res:   T     = T::try_from(v).map_err(|_| VarintError::Overflow)?;
+    *pos = p;
+    Ok(res)
+}
+
+/// Appends the Elias delta code of a positive integer: the length of `n` in Elias gamma code, followed by `n` without its leading `1`.
+/// Shorter than gamma codes for all but the smallest values.
+/// # Arguments
+/// * `value` - The integer.
+/// * `out` - The `BitVec` to append to.
+/// # Panics
+/// Panics if `value` is `0`, which Elias codes can't represent.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::varint::{write_gamma, write_delta, read_delta};
+/// use bit_vec::BitVec;
+/// let (mut gamma, mut delta) = (BitVec::new(), BitVec::new());
+/// write_gamma(1_000_000u32, &mut gamma);
+/// write_delta(1_000_000u32, &mut delta);
+/// assert_eq!((gamma.len(), delta.len()), (39, 28));
+///
+/// assert_eq!(read_delta::<u32>(&delta, &mut 0), Ok(1_000_000));
+/// ```
+pub fn write_delta<T: Unsigned>(value: T, out: &mut BitVec) {
+    let v: u128 = value.into();
+    if v == 0
+    { panic!("Elias codes can't represent 0."); }
+
+    let len: u32 = 128 - v.leading_zeros();
+    write_gamma(len, out);
+    push_bits(out, v, len - 1);
+}
+
+/// Reads an Elias delta code.
+/// # Arguments
+/// * `bits` - The `BitVec`.
+/// * `pos` - The position of the first bit. Advanced past the code on success.
+/// # Returns
+/// A `Result<T, VarintError>`.
+pub fn read_delta<T: Unsigned>(bits: &BitVec, pos: &mut usize) -> Result<T, VarintError> {
+    let mut p: usize = *pos;
+    let len:   u128  = gamma_value(bits, &mut p)?;
+    if len > 128
+    { return Err(VarintError::Overflow); }
+
+    let v:   u128 = 1 << (len - 1) | read_bits(bits, &mut p, len as u32 - 1)?;
+    let res: T    = T::try_from(v).map_err(|_| VarintError::Overflow)?;
+    *pos = p;
+    Ok(res)
+}
+
+/// Appends the Golomb-Rice code of an integer with parameter `k`: `n >> k` in unary (that many `1`s and a `0`), followed by the low `k` bits.
+/// Optimal for geometrically distributed values with a mean of about `2^k`.
+/// # Arguments
+/// * `value` - The integer.
+/// * `k` - The number of low bits written in binary.
+/// * `out` - The `BitVec` to append to.
+/// # Panics
+/// Panics if `k` is greater than `127`.
+/// The quotient `value >> k` is written as that many bits, so this panics if it doesn't fit into a `usize` or the bits can't be allocated.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::varint::{write_rice, read_rice};
+/// use bit_vec::BitVec;
+/// let mut bits = BitVec::new();
+/// for v in [3u16, 9, 0, 17].iter() {
+///     write_rice(*v, 3, &mut bits);
+/// }
+/// assert_eq!(bits.len(), 4 + 5 + 4 + 6);
+///
+/// let mut pos = 0;
+/// let res: Vec<u16> = (0..4).map(|_| read_rice(&bits, &mut pos, 3).unwrap()).collect();
+/// assert_eq!(res, [3, 9, 0, 17]);
+/// ```
+pub fn write_rice<T: Unsigned>(value: T, k: u32, out: &mut BitVec) {
+    if k > 127
+    { panic!("The Rice parameter must be in the range of 0 to 127."); }
+
+    let v: u128 = value.into();
+    out.grow(usize::try_from(v >> k).expect("The quotient is too large to write in unary."), true);
+    out.push(false);
+    push_bits(out, v, k);
+}
+
+/// Reads a Golomb-Rice code.
+/// # Arguments
+/// * `bits` - The `BitVec`.
+/// * `pos` - The position of the first bit. Advanced past the code on success.
+/// * `k` - The parameter the code was written with.
+/// # Returns
+/// A `Result<T, VarintError>`.
+/// # Panics
+/// Panics if `k` is greater than `127`.
+pub fn read_rice<T: Unsigned>(bits: &BitVec, pos: &mut usize, k: u32) -> Result<T, VarintError> {
+    if k > 127
+    { panic!("The Rice parameter must be in the range of 0 to 127."); }
+
+    let mut p: usize = *pos;
+    let q:     u128  = read_unary(bits, &mut p)?;
+    let v:     u128  = q.checked_shl(k)
+                        .filter(|x| x >> k == q)
+                        .ok_or(VarintError::Overflow)?
+                     | read_bits(bits, &mut p, k)?;
+    let res:   T     = T::try_from(v).map_err(|_| VarintError::Overflow)?;
+    *pos = p;
+    Ok(res)
+}
+
+/// Appends the Golomb code of an integer with any positive parameter `m`: `n / m` in unary, followed by `n % m` in truncated binary.
+/// If `m` is a power of two, this is the same as `write_rice`.
+/// # Arguments
+/// * `value` - The integer.
+/// * `m` - The divisor.
+/// * `out` - The `BitVec` to append to.
+/// # Panics
+/// Panics if `m` is `0`.
+/// The quotient `value / m` is written as that many bits, so this panics if it doesn't fit into a `usize` or the bits can't be allocated.
+/// # Examples
+/// ```
+/// use lib_rapid::compsci::varint::{write_golomb, read_golomb, write_rice};
+/// use bit_vec::BitVec;
+/// let mut bits = BitVec::new();
+/// write_golomb(42u64, 10, &mut bits);
+/// // 4 in unary, then 2 in 3 bits as 2 < 16 - 10.
+/// assert_eq!(bits, BitVec::from_fn(8, |i| [true, true, true, true, false, false, true, false][i]));
+///
+/// assert_eq!(read_golomb::<u64>(&bits, &mut 0, 10), Ok(42));
+///
+/// // Powers of two give the Rice codes.
+/// for (m, k) in [(1u128, 0), (2, 1), (4, 2), (1 << 127, 127)].iter() {
+///     for v in [0u128, 5, 1 << 100].iter().filter(|v| **v >> *k < 64) {
+///         let (mut golomb, mut rice) = (BitVec::new(), BitVec::new());
+///         write_golomb(*v, *m, &mut golomb);
+///         write_rice(*v, *k, &mut rice);
+///         assert_eq!(golomb, rice);
+///         assert_eq!(read_golomb(&golomb, &mut 0, *m), Ok(*v));
+///     }
+/// }
+///
+/// // The largest divisor leaves one short remainder of 127 bits, the rest get 128.
+/// for (v, len) in [(0u128, 1 + 127), (u128::MAX - 1, 1 + 128), (u128::MAX, 2 + 127)].iter() {
+///     let mut bits = BitVec::new();
+///     write_golomb(*v, u128::MAX, &mut bits);
+///     assert_eq!(bits.len(), *len);
+///     assert_eq!(read_golomb(&bits, &mut 0, u128::MAX), Ok(*v));
+/// }
+/// ```
+pub fn write_golomb<T: Unsigned>(value: T, m: T, out: &mut BitVec) {
+    let (v, m): (u128, u128) = (value.into(), m.into());
+    if m == 0
+    { panic!("The Golomb parameter must be positive."); }
+
+    out.grow(usize::try_from(v / m).expect("The quotient is too large to write in unary."), true);
+    out.push(false);
+
+    let (b, cutoff) = truncated_binary(m);
+    let r:   u128   = v % m;
+    if r < cutoff
+    { push_bits(out, r, b - 1); }
+    else
+    { push_bits(out, r + cutoff, b); }
+}
+
+/// Reads a Golomb code.
+/// # Arguments
+/// * `bits` - The `BitVec`.
+/// * `pos` - The position of the first bit. Advanced past the code on success.
+/// * `m` - The parameter the code was written with.
+/// # Returns
+/// A `Result<T, VarintError>`.
+/// # Panics
+/// Panics if `m` is `0`.
+pub fn read_golomb<T: Unsigned>(bits: &BitVec, pos: &mut usize, m: T) -> Result<T, VarintError> {
+    let m: u128 = m.into();
+    if m == 0
+    { panic!("The Golomb parameter must be positive."); }
+
+    let mut p:      usize = *pos;
+    let q:          u128  = read_unary(bits, &mut p)?;
+    let (b, cutoff)       = truncated_binary(m);
+    let mut r:      u128  = read_bits(bits, &mut p, b - 1)?;
+    if r >= cutoff
+    { r = (r << 1 | read_bits(bits, &mut p, 1)?) - cutoff; }
+
+    let v:   u128 = q.checked_mul(m)
+                     .and_then(|x| x.checked_add(r))
+                     .ok_or(VarintError::Overflow)?;
+    let res: T    = T::try_from(v).map_err(|_| VarintError::Overflow)?;
+    *pos = p;
+    Ok(res)
+}
+
+/// The number of bits `b` of the longer remainders and the number of remainders which get `b - 1` bits.
+fn truncated_binary(m: u128) -> (u32, u128) {
+    let b: u32 = (128 - (m - 1).leading_zeros()).max(1);
+    // `b` can be 128, where `1 << b` would overflow, and `2^b - 1 - m` underflows for powers of two.
+    (b, (u128::MAX >> (128 - b)) - (m - 1))
+}
+
+/// Appends the lowest `n` bits of `value`, most significant first.
+fn push_bits(out: &mut BitVec, value: u128, n: u32) {
+    for i in (0..n).rev()
+    { out.push((value >> i) & 1 == 1); }
+}
+
+/// Reads `n` bits, most significant first.
+fn read_bits(bits: &BitVec, pos: &mut usize, n: u32) -> Result<u128, VarintError> {
+    if bits.len().saturating_sub(*pos) < n as usize
+    { return Err(VarintError::Truncated); }
+
+    let mut v: u128 = 0;
+    for i in 0..n as usize
+    { v = v << 1 | bits[*pos + i] as u128; }
+    *pos += n as usize;
+    Ok(v)
+}
+
+/// Reads `1`s up to the next `0`.
+fn read_unary(bits: &BitVec, pos: &mut usize) -> Result<u128, VarintError> {
+    let ones: usize = (*pos..bits.len()).take_while(|i| bits[*i]).count();
+    if *pos + ones >= bits.len()
+    { return Err(VarintError::Truncated); }
+    *pos += ones + 1;
+    Ok(ones as u128)
+}
+
+/// Reads an Elias gamma code as a `u128`.
+fn gamma_value(bits: &BitVec, pos: &mut usize) -> Result<u128, VarintError> {
+    let zeros: usize = (*pos..bits.len()).take(128).take_while(|i| !bits[*i]).count();
+    if zeros > 127
+    { return Err(VarintError::Overflow); }
+
+    let mut p: usize = *pos + zeros;
+    let v:     u128  = read_bits(bits, &mut p, zeros as u32 + 1)?;
+    *pos = p;
+    Ok(v)
+}
\ No newline at end of file